@@ -8,3 +8,6 @@ pub const AUTHORITY_SEED: &str = "authority";
 
 #[constant]
 pub const LIQUIDITY_SEED: &str = "liquidity";
+
+#[constant]
+pub const LOCKED_LIQUIDITY_SEED: &str = "locked_liquidity";
@@ -14,6 +14,54 @@ pub enum CustomError {
     #[msg("Result is below the minimum expected")]
     SwapResultUnderflow,
 
+    #[msg("Liquidity result is below the minimum expected")]
+    LiquidityResultUnderflow,
+
+    #[msg("Required input is above the maximum allowed")]
+    SwapInputOverflow,
+
+    #[msg("Pool has insufficient liquidity for the requested output")]
+    InsufficientLiquidity,
+
+    #[msg("Invalid swap route")]
+    InvalidRoute,
+
+    #[msg("Not enough price observations for the requested window")]
+    InsufficientObservations,
+
+    #[msg("Protocol fee recipient account is missing or invalid")]
+    InvalidFeeRecipient,
+
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+
+    #[msg("Operation is paused")]
+    Paused,
+
+    #[msg("Token mint has an unsupported extension")]
+    UnsupportedMintExtension,
+
+    #[msg("Invalid flash swap callback program")]
+    InvalidCallbackProgram,
+
+    #[msg("Pool is locked by an outstanding flash loan")]
+    FlashLocked,
+
+    #[msg("Flash loan is not repaid within the same transaction")]
+    FlashRepayMissing,
+
+    #[msg("Pool has no outstanding flash loan")]
+    NoFlashLoan,
+
+    #[msg("Transaction has expired")]
+    Expired,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Division by zero")]
+    DivisionByZero,
+
     #[msg("Invariant check failed")]
     InvariantError,
 }
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AmmInitialized {
+    pub amm: Pubkey,
+    pub id: Pubkey,
+    pub admin: Pubkey,
+    pub fee: u16,
+}
+
+#[event]
+pub struct PoolCreated {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub fee_bps: Option<u16>,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct Swapped {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub swap_a: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_paid: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct FlashSwapped {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount_a_out: u64,
+    pub amount_b_out: u64,
+    pub amount_a_in: u64,
+    pub amount_b_in: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct FlashLoanBorrowed {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct FlashLoanRepaid {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub fee_a: u64,
+    pub fee_b: u64,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct FeeUpdated {
+    pub amm: Pubkey,
+    pub old_fee: u16,
+    pub new_fee: u16,
+}
+
+#[event]
+pub struct FeeToUpdated {
+    pub amm: Pubkey,
+    pub old_fee_to: Option<Pubkey>,
+    pub new_fee_to: Option<Pubkey>,
+    pub protocol_fee_share: u8,
+}
+
+#[event]
+pub struct MaxPoolFeeUpdated {
+    pub amm: Pubkey,
+    pub old_max_pool_fee: u16,
+    pub new_max_pool_fee: u16,
+}
+
+#[event]
+pub struct PoolFeeUpdated {
+    pub pool: Pubkey,
+    pub old_fee_bps: Option<u16>,
+    pub new_fee_bps: Option<u16>,
+}
+
+#[event]
+pub struct Paused {
+    pub amm: Pubkey,
+    pub pool: Option<Pubkey>,
+    pub allow_withdrawals: bool,
+}
+
+#[event]
+pub struct Unpaused {
+    pub amm: Pubkey,
+    pub pool: Option<Pubkey>,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub amm: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub amm: Pubkey,
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminRenounced {
+    pub amm: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct FlashFeeUpdated {
+    pub pool: Pubkey,
+    pub old_flash_fee_bps: u16,
+    pub new_flash_fee_bps: u16,
+}
+
+#[event]
+pub struct Synced {
+    pub pool: Pubkey,
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct Skimmed {
+    pub pool: Pubkey,
+    pub recipient_a: Pubkey,
+    pub recipient_b: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
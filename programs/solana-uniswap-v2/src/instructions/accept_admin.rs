@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::AdminTransferred, state::Amm};
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let amm = &mut self.amm;
+        let previous_admin = amm.admin;
+        amm.admin = self.pending_admin.key();
+        amm.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferred {
+            amm: amm.key(),
+            previous_admin,
+            new_admin: amm.admin,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = pending_admin @ CustomError::Unauthorized,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Proposed admin authority
+    pub pending_admin: Signer<'info>,
+}
@@ -1,15 +1,21 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, MintTo, Token, TokenAccount, Transfer},
+    token::Token,
+    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
 };
-use fixed::types::I64F64;
-use fixed_sqrt::FixedSqrt;
 
 use crate::{
     constants::{AUTHORITY_SEED, LIQUIDITY_SEED, MIN_LIQUIDITY},
     errors::CustomError,
-    state::Pool,
+    events::LiquidityAdded,
+    math::{
+        amount_after_fee, constant_product_output, liquidity_for_amounts, liquidity_for_deposit,
+        quote, single_sided_swap_amount,
+    },
+    state::{Amm, Pool},
+    token::{is_native_mint, transfer_fee, wrap_sol},
+    utils::check_expiry,
 };
 
 impl<'info> DepositLiquidity<'info> {
@@ -17,92 +23,224 @@ impl<'info> DepositLiquidity<'info> {
         &mut self,
         amount_a: u64,
         amount_b: u64,
+        min_liquidity_out: u64,
+        expiry_unix_ts: i64,
         bumps: &DepositLiquidityBumps,
     ) -> Result<()> {
-        // Ensure owned assets are only being deposited
-        let mut amount_a = if amount_a > self.depositor_account_a.amount {
-            self.depositor_account_a.amount
-        } else {
+        check_expiry(expiry_unix_ts)?;
+
+        // Ensure owned assets are only being deposited, native SOL is wrapped
+        // from the depositor's lamports once the amounts are final
+        let mut amount_a = if is_native_mint(&self.mint_a.key()) {
             amount_a
-        };
-        let mut amount_b = if amount_b > self.depositor_account_b.amount {
-            self.depositor_account_b.amount
         } else {
+            amount_a.min(self.depositor_account_a.amount)
+        };
+        let mut amount_b = if is_native_mint(&self.mint_b.key()) {
             amount_b
+        } else {
+            amount_b.min(self.depositor_account_b.amount)
         };
 
+        // Update price oracle with pre-deposit reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Mint protocol share of the fees accrued since the last liquidity event
+        let authority_bump = bumps.pool_authority;
+        let authority_seeds = &[
+            &self.pool.amm.to_bytes(),
+            &self.mint_a.key().to_bytes(),
+            &self.mint_b.key().to_bytes(),
+            AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+        let fee_on = self.pool.mint_protocol_fee(
+            &self.amm,
+            &mut self.mint_liquidity,
+            &self.fee_to_account_liquidity,
+            &self.pool_authority,
+            &self.token_program,
+            signer_seeds,
+        )?;
+
         // Ensure deposit ratio with existing pool liquidity
-        let pool_a = &self.pool_account_a;
-        let pool_b = &self.pool_account_b;
-        // CAUTION: Current pool creation logic vulnerable to frontrunning attacks
-        let pool_creation = pool_a.amount == 0 && pool_b.amount == 0;
+        let (reserve_a, reserve_b) = (self.pool.reserve_a, self.pool.reserve_b);
+        // CAUTION: Pool creation through a separate deposit can be frontrun, use
+        // `initialize_pool_with_liquidity` to set the initial price atomically
+        // Tokens synced into a single side before the first deposit set no price
+        let pool_creation = reserve_a == 0 || reserve_b == 0;
         (amount_a, amount_b) = if pool_creation {
-            // Add as is if there is no liquidity
+            // Add as is if there is no price to match
             (amount_a, amount_b)
         } else {
-            let ratio = I64F64::from_num(pool_a.amount)
-                .checked_mul(I64F64::from_num(pool_b.amount))
-                .unwrap();
-            if pool_a.amount > pool_b.amount {
-                (
-                    I64F64::from_num(amount_b)
-                        .checked_mul(ratio)
-                        .unwrap()
-                        .to_num::<u64>(),
-                    amount_b,
-                )
+            // Take the most of both maxima that fits the reserves ratio
+            let amount_b_optimal = quote(amount_a, reserve_a, reserve_b)?;
+            if amount_b_optimal <= amount_b {
+                (amount_a, amount_b_optimal)
             } else {
-                (
-                    amount_a,
-                    I64F64::from_num(amount_a)
-                        .checked_div(ratio)
-                        .unwrap()
-                        .to_num::<u64>(),
-                )
+                (quote(amount_b, reserve_b, reserve_a)?, amount_b)
             }
         };
 
-        // Calculate liquidity tokens to be deposited
-        let mut liquidity = I64F64::from_num(amount_a)
-            .checked_mul(I64F64::from_num(amount_b))
-            .unwrap()
-            .sqrt()
-            .to_num::<u64>();
-
-        // Reserve minimum liquidity for initial deposit to prevent draining
-        if pool_creation {
+        // Calculate liquidity tokens to be deposited from the amounts credited
+        // to the pool after transfer fees
+        let received_a = amount_a - transfer_fee(&self.mint_a.to_account_info(), amount_a)?;
+        let received_b = amount_b - transfer_fee(&self.mint_b.to_account_info(), amount_b)?;
+        let liquidity = if pool_creation {
+            // Minimum liquidity locked at pool creation is deducted from the initial deposit
+            let liquidity = liquidity_for_amounts(received_a, received_b);
             if liquidity < MIN_LIQUIDITY {
                 return err!(CustomError::InsufficientDepositAmount);
             }
 
-            liquidity -= MIN_LIQUIDITY;
+            liquidity - MIN_LIQUIDITY
+        } else {
+            liquidity_for_deposit(
+                received_a,
+                received_b,
+                reserve_a,
+                reserve_b,
+                self.mint_liquidity.supply,
+            )?
+        };
+
+        // Ensure minimum liquidity requirement is met
+        if liquidity < min_liquidity_out {
+            return err!(CustomError::LiquidityResultUnderflow);
         }
 
+        // Wrap the missing lamports when depositing native SOL
+        wrap_sol(
+            &self.depositor,
+            &mut self.depositor_account_a,
+            &self.token_program_a,
+            &self.system_program,
+            amount_a,
+        )?;
+        wrap_sol(
+            &self.depositor,
+            &mut self.depositor_account_b,
+            &self.token_program_b,
+            &self.system_program,
+            amount_b,
+        )?;
+
         // Execute token transfer to pool accounts
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new(
-                self.token_program.to_account_info(),
-                Transfer {
+                self.token_program_a.to_account_info(),
+                TransferChecked {
                     from: self.depositor_account_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
                     to: self.pool_account_a.to_account_info(),
                     authority: self.depositor.to_account_info(),
                 },
             ),
             amount_a,
+            self.mint_a.decimals,
         )?;
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new(
-                self.token_program.to_account_info(),
-                Transfer {
+                self.token_program_b.to_account_info(),
+                TransferChecked {
                     from: self.depositor_account_b.to_account_info(),
+                    mint: self.mint_b.to_account_info(),
                     to: self.pool_account_b.to_account_info(),
                     authority: self.depositor.to_account_info(),
                 },
             ),
             amount_b,
+            self.mint_b.decimals,
         )?;
 
         // Mint and distribute liquidity tokens to depositor
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint_liquidity.to_account_info(),
+                    to: self.depositor_account_liquidity.to_account_info(),
+                    authority: self.pool_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            liquidity,
+        )?;
+
+        // Move the reserves by the transferred amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        // Checkpoint reserves product for the next protocol fee computation
+        self.pool.checkpoint_k_last(fee_on);
+
+        emit!(LiquidityAdded {
+            pool: self.pool.key(),
+            user: self.depositor.key(),
+            amount_a,
+            amount_b,
+            liquidity,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
+        Ok(())
+    }
+
+    pub fn deposit_single_sided(
+        &mut self,
+        deposit_a: bool,
+        amount_in: u64,
+        min_liquidity_out: u64,
+        expiry_unix_ts: i64,
+        bumps: &DepositLiquidityBumps,
+    ) -> Result<()> {
+        check_expiry(expiry_unix_ts)?;
+
+        // Wrap the missing lamports when depositing native SOL
+        wrap_sol(
+            &self.depositor,
+            if deposit_a {
+                &mut self.depositor_account_a
+            } else {
+                &mut self.depositor_account_b
+            },
+            if deposit_a {
+                &self.token_program_a
+            } else {
+                &self.token_program_b
+            },
+            &self.system_program,
+            amount_in,
+        )?;
+
+        // Ensure owned assets are only being deposited
+        let balance_in = if deposit_a {
+            self.depositor_account_a.amount
+        } else {
+            self.depositor_account_b.amount
+        };
+        let amount_in = if amount_in > balance_in {
+            balance_in
+        } else {
+            amount_in
+        };
+
+        // Single sided deposits need existing liquidity to swap against
+        let (reserve_in, reserve_out) = if deposit_a {
+            (self.pool.reserve_a, self.pool.reserve_b)
+        } else {
+            (self.pool.reserve_b, self.pool.reserve_a)
+        };
+        if reserve_in == 0 || reserve_out == 0 {
+            return err!(CustomError::InsufficientLiquidity);
+        }
+
+        // Update price oracle with pre-deposit reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Mint protocol share of the fees accrued since the last liquidity event
         let authority_bump = bumps.pool_authority;
         let authority_seeds = &[
             &self.pool.amm.to_bytes(),
@@ -112,7 +250,77 @@ impl<'info> DepositLiquidity<'info> {
             &[authority_bump],
         ];
         let signer_seeds = &[&authority_seeds[..]];
-        token::mint_to(
+        let fee_on = self.pool.mint_protocol_fee(
+            &self.amm,
+            &mut self.mint_liquidity,
+            &self.fee_to_account_liquidity,
+            &self.pool_authority,
+            &self.token_program,
+            signer_seeds,
+        )?;
+
+        // Swap the share of the credited input that leaves the remainder in
+        // the post-swap pool ratio, the swapped tokens never leave the pool
+        let mint_in = if deposit_a {
+            self.mint_a.to_account_info()
+        } else {
+            self.mint_b.to_account_info()
+        };
+        let received = amount_in - transfer_fee(&mint_in, amount_in)?;
+        let fee = self.pool.effective_fee(&self.amm);
+        let swap_amount = single_sided_swap_amount(received, reserve_in, fee)?;
+        let taxed_swap = amount_after_fee(swap_amount, fee)?;
+        let swap_result = constant_product_output(taxed_swap, reserve_in, reserve_out)?;
+
+        // Calculate liquidity tokens for the remainder at post-swap reserves
+        let liquidity = liquidity_for_deposit(
+            received - swap_amount,
+            swap_result,
+            reserve_in
+                .checked_add(swap_amount)
+                .ok_or(CustomError::MathOverflow)?,
+            reserve_out - swap_result,
+            self.mint_liquidity.supply,
+        )?;
+
+        // Ensure minimum liquidity requirement is met
+        if liquidity < min_liquidity_out {
+            return err!(CustomError::LiquidityResultUnderflow);
+        }
+
+        // Execute token transfer to the pool account
+        if deposit_a {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
+                        from: self.depositor_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
+                        to: self.pool_account_a.to_account_info(),
+                        authority: self.depositor.to_account_info(),
+                    },
+                ),
+                amount_in,
+                self.mint_a.decimals,
+            )?;
+        } else {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
+                        from: self.depositor_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
+                        to: self.pool_account_b.to_account_info(),
+                        authority: self.depositor.to_account_info(),
+                    },
+                ),
+                amount_in,
+                self.mint_b.decimals,
+            )?;
+        }
+
+        // Mint and distribute liquidity tokens to depositor
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 MintTo {
@@ -125,22 +333,52 @@ impl<'info> DepositLiquidity<'info> {
             liquidity,
         )?;
 
+        // Move the reserves by the transferred amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        // Checkpoint reserves product for the next protocol fee computation
+        self.pool.checkpoint_k_last(fee_on);
+
+        emit!(LiquidityAdded {
+            pool: self.pool.key(),
+            user: self.depositor.key(),
+            amount_a: if deposit_a { amount_in } else { 0 },
+            amount_b: if deposit_a { 0 } else { amount_in },
+            liquidity,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
         Ok(())
     }
 }
 #[derive(Accounts)]
 pub struct DepositLiquidity<'info> {
     #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        constraint = !amm.paused @ CustomError::Paused,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
         seeds = [
             pool.amm.as_ref(),
             pool.mint_a.key().as_ref(),
             pool.mint_b.key().as_ref(),
         ],
         bump,
+        has_one = amm,
         has_one = mint_a,
         has_one = mint_b,
+        constraint = !pool.paused @ CustomError::Paused,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: Box<Account<'info, Pool>>,
 
     /// CHECK: Read only authority
     #[account(
@@ -154,7 +392,8 @@ pub struct DepositLiquidity<'info> {
     )]
     pub pool_authority: AccountInfo<'info>,
 
-    /// Payer
+    /// Payer, depositing native SOL directly
+    #[account(mut)]
     pub depositor: Signer<'info>,
 
     #[account(
@@ -167,25 +406,29 @@ pub struct DepositLiquidity<'info> {
         ],
         bump,
     )]
-    pub mint_liquidity: Box<Account<'info, Mint>>,
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
 
-    pub mint_a: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
 
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
     )]
-    pub pool_account_a: Box<Account<'info, TokenAccount>>,
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
     )]
-    pub pool_account_b: Box<Account<'info, TokenAccount>>,
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
@@ -193,23 +436,39 @@ pub struct DepositLiquidity<'info> {
         associated_token::mint = mint_liquidity,
         associated_token::authority = depositor,
     )]
-    pub depositor_account_liquidity: Box<Account<'info, TokenAccount>>,
+    pub depositor_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint_a,
         associated_token::authority = depositor,
+        associated_token::token_program = token_program_a,
     )]
-    pub depositor_account_a: Box<Account<'info, TokenAccount>>,
+    pub depositor_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint_b,
         associated_token::authority = depositor,
+        associated_token::token_program = token_program_b,
+    )]
+    pub depositor_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Protocol fee recipient, only used as the liquidity account owner
+    #[account(address = amm.fee_to @ CustomError::InvalidFeeRecipient)]
+    pub fee_to: AccountInfo<'info>,
+
+    /// Protocol fee recipient liquidity account, created when first needed so
+    /// that minting the protocol fee never blocks liquidity providers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = fee_to,
     )]
-    pub depositor_account_b: Box<Account<'info, TokenAccount>>,
+    pub fee_to_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Payer
     #[account(mut)]
@@ -217,6 +476,8 @@ pub struct DepositLiquidity<'info> {
 
     /// Solana accounts
     pub token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
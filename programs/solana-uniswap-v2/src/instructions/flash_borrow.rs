@@ -0,0 +1,212 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{
+        self, load_current_index_checked, load_instruction_at_checked,
+    },
+    Discriminator,
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    constants::AUTHORITY_SEED,
+    errors::*,
+    events::FlashLoanBorrowed,
+    state::{Amm, Pool},
+};
+
+/// Position of the pool in the `FlashRepay` accounts
+const FLASH_REPAY_POOL_INDEX: usize = 1;
+
+impl<'info> FlashBorrow<'info> {
+    pub fn flash_borrow(
+        &mut self,
+        amount_a: u64,
+        amount_b: u64,
+        bumps: &FlashBorrowBumps,
+    ) -> Result<()> {
+        if (amount_a == 0 && amount_b == 0)
+            || amount_a >= self.pool.reserve_a
+            || amount_b >= self.pool.reserve_b
+        {
+            return err!(CustomError::InsufficientLiquidity);
+        }
+
+        // Borrowing must be a top level instruction followed by a repayment
+        // of this very pool later in the same transaction
+        let current_index = load_current_index_checked(&self.instructions)? as usize;
+        let current = load_instruction_at_checked(current_index, &self.instructions)?;
+        if current.program_id != crate::ID {
+            return err!(CustomError::FlashRepayMissing);
+        }
+        let mut index = current_index + 1;
+        loop {
+            let instruction = match load_instruction_at_checked(index, &self.instructions) {
+                Ok(instruction) => instruction,
+                Err(_) => return err!(CustomError::FlashRepayMissing),
+            };
+            if instruction.program_id == crate::ID
+                && instruction
+                    .data
+                    .starts_with(&crate::instruction::FlashRepay::DISCRIMINATOR)
+                && instruction
+                    .accounts
+                    .get(FLASH_REPAY_POOL_INDEX)
+                    .map(|meta| meta.pubkey)
+                    == Some(self.pool.key())
+            {
+                break;
+            }
+            index += 1;
+        }
+
+        // Update price oracle with pre-loan reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Lock the pool until the loan is repaid
+        self.pool.flash_locked = true;
+        self.pool.flash_amount_a = amount_a;
+        self.pool.flash_amount_b = amount_b;
+
+        // Transfer the borrowed tokens to the receiver
+        let authority_bump = bumps.pool_authority;
+        let authority_seeds = &[
+            &self.pool.amm.to_bytes(),
+            &self.mint_a.key().to_bytes(),
+            &self.mint_b.key().to_bytes(),
+            AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+        if amount_a > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
+                        to: self.receiver_account_a.to_account_info(),
+                        authority: self.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount_a,
+                self.mint_a.decimals,
+            )?;
+        }
+        if amount_b > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
+                        to: self.receiver_account_b.to_account_info(),
+                        authority: self.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount_b,
+                self.mint_b.decimals,
+            )?;
+        }
+
+        // Move the reserves by the lent amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        emit!(FlashLoanBorrowed {
+            pool: self.pool.key(),
+            user: self.borrower.key(),
+            amount_a,
+            amount_b,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        constraint = !amm.paused @ CustomError::Paused,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !pool.paused @ CustomError::Paused,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// The account taking the flash loan
+    pub borrower: Signer<'info>,
+
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::token_program = token_program_a,
+    )]
+    pub receiver_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::token_program = token_program_b,
+    )]
+    pub receiver_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Instructions sysvar used to find the repayment
+    #[account(address = instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    /// Solana accounts
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    constants::AUTHORITY_SEED,
+    errors::*,
+    events::FlashLoanRepaid,
+    math::mul_div_ceil,
+    state::{Amm, Pool},
+    token::transfer_inverse_fee,
+};
+
+impl<'info> FlashRepay<'info> {
+    pub fn flash_repay(&mut self) -> Result<()> {
+        let amount_a = self.pool.flash_amount_a;
+        let amount_b = self.pool.flash_amount_b;
+
+        // Flash fee is rounded up in favor of the pool
+        let flash_fee_bps = self.pool.flash_fee_bps as u64;
+        let fee_a = mul_div_ceil(amount_a, flash_fee_bps, 10000)?;
+        let fee_b = mul_div_ceil(amount_b, flash_fee_bps, 10000)?;
+
+        // Repay principal and fee, so that the pool is credited in full after
+        // transfer fees
+        if amount_a > 0 {
+            let due = amount_a
+                .checked_add(fee_a)
+                .ok_or(CustomError::MathOverflow)?;
+            let amount = due
+                .checked_add(transfer_inverse_fee(&self.mint_a.to_account_info(), due)?)
+                .ok_or(CustomError::MathOverflow)?;
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
+                        from: self.repayer_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
+                        to: self.pool_account_a.to_account_info(),
+                        authority: self.repayer.to_account_info(),
+                    },
+                ),
+                amount,
+                self.mint_a.decimals,
+            )?;
+        }
+        if amount_b > 0 {
+            let due = amount_b
+                .checked_add(fee_b)
+                .ok_or(CustomError::MathOverflow)?;
+            let amount = due
+                .checked_add(transfer_inverse_fee(&self.mint_b.to_account_info(), due)?)
+                .ok_or(CustomError::MathOverflow)?;
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
+                        from: self.repayer_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
+                        to: self.pool_account_b.to_account_info(),
+                        authority: self.repayer.to_account_info(),
+                    },
+                ),
+                amount,
+                self.mint_b.decimals,
+            )?;
+        }
+
+        // Release the pool
+        let pool = &mut self.pool;
+        pool.flash_locked = false;
+        pool.flash_amount_a = 0;
+        pool.flash_amount_b = 0;
+
+        // Move the reserves by the repaid amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        emit!(FlashLoanRepaid {
+            pool: self.pool.key(),
+            user: self.repayer.key(),
+            amount_a,
+            amount_b,
+            fee_a,
+            fee_b,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = pool.flash_locked @ CustomError::NoFlashLoan,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// The account repaying the flash loan
+    pub repayer: Signer<'info>,
+
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::token_program = token_program_a,
+    )]
+    pub repayer_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::token_program = token_program_b,
+    )]
+    pub repayer_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Solana accounts
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+}
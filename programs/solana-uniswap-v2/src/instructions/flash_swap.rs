@@ -0,0 +1,230 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
+    },
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    constants::AUTHORITY_SEED,
+    errors::*,
+    events::FlashSwapped,
+    math::verify_invariant,
+    state::{Amm, Pool},
+};
+
+impl<'info> FlashSwap<'info> {
+    pub fn flash_swap(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        amount_a_out: u64,
+        amount_b_out: u64,
+        data: Vec<u8>,
+        bumps: &FlashSwapBumps,
+    ) -> Result<()> {
+        // The callback must not be able to reenter the pool mid-swap
+        if self.callback_program.key() == crate::ID {
+            return err!(CustomError::InvalidCallbackProgram);
+        }
+
+        let reserve_a = self.pool.reserve_a;
+        let reserve_b = self.pool.reserve_b;
+        if (amount_a_out == 0 && amount_b_out == 0)
+            || amount_a_out >= reserve_a
+            || amount_b_out >= reserve_b
+        {
+            return err!(CustomError::InsufficientLiquidity);
+        }
+
+        // Update price oracle with pre-swap reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Optimistically transfer the requested outputs to the receiver
+        let authority_bump = bumps.pool_authority;
+        let authority_seeds = &[
+            &self.pool.amm.to_bytes(),
+            &self.mint_a.key().to_bytes(),
+            &self.mint_b.key().to_bytes(),
+            AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+        if amount_a_out > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
+                        to: self.receiver_account_a.to_account_info(),
+                        authority: self.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount_a_out,
+                self.mint_a.decimals,
+            )?;
+        }
+        if amount_b_out > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
+                        to: self.receiver_account_b.to_account_info(),
+                        authority: self.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount_b_out,
+                self.mint_b.decimals,
+            )?;
+        }
+
+        // Hand control to the callback, which must pay the pool back
+        let callback = Instruction {
+            program_id: self.callback_program.key(),
+            accounts: remaining_accounts
+                .iter()
+                .map(|account| AccountMeta {
+                    pubkey: account.key(),
+                    is_signer: account.is_signer,
+                    is_writable: account.is_writable,
+                })
+                .collect(),
+            data,
+        };
+        let mut callback_accounts = remaining_accounts.to_vec();
+        callback_accounts.push(self.callback_program.to_account_info());
+        invoke(&callback, &callback_accounts)?;
+
+        // Derive the inputs from the reserves left after the callback
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+        let balance_a = self.pool.reserve_a;
+        let balance_b = self.pool.reserve_b;
+        let amount_a_in = balance_a.saturating_sub(reserve_a - amount_a_out);
+        let amount_b_in = balance_b.saturating_sub(reserve_b - amount_b_out);
+
+        // Verify the fee-adjusted invariant, charging the fee on the inputs
+        verify_invariant(
+            reserve_a,
+            reserve_b,
+            balance_a,
+            balance_b,
+            amount_a_in,
+            amount_b_in,
+            self.pool.effective_fee(&self.amm),
+        )?;
+
+        msg!(
+            "Flash swapped {} A and {} B for {} A and {} B",
+            amount_a_out,
+            amount_b_out,
+            amount_a_in,
+            amount_b_in
+        );
+
+        emit!(FlashSwapped {
+            pool: self.pool.key(),
+            user: self.initiator.key(),
+            amount_a_out,
+            amount_b_out,
+            amount_a_in,
+            amount_b_in,
+            reserve_a: balance_a,
+            reserve_b: balance_b,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        constraint = !amm.paused @ CustomError::Paused,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !pool.paused @ CustomError::Paused,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// The account initiating the flash swap
+    pub initiator: Signer<'info>,
+
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::token_program = token_program_a,
+    )]
+    pub receiver_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::token_program = token_program_b,
+    )]
+    pub receiver_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Arbitrary program called back to repay the pool
+    #[account(executable)]
+    pub callback_program: AccountInfo<'info>,
+
+    /// Solana accounts
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::*, state::Amm};
+use crate::{errors::*, events::AmmInitialized, state::Amm};
 
 impl<'info> InitializeAmm<'info> {
     pub fn initialize_amm(&mut self, id: Pubkey, fee: u16) -> Result<()> {
@@ -8,6 +8,15 @@ impl<'info> InitializeAmm<'info> {
         amm.id = id;
         amm.admin = self.admin.key();
         amm.fee = fee;
+        // Pools can only undercut the AMM fee until the admin raises the cap
+        amm.max_pool_fee = fee;
+
+        emit!(AmmInitialized {
+            amm: amm.key(),
+            id,
+            admin: amm.admin,
+            fee,
+        });
 
         Ok(())
     }
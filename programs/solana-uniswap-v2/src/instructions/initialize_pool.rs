@@ -1,26 +1,136 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token::Token,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    },
+    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface},
 };
 
 use crate::{
-    constants::{AUTHORITY_SEED, LIQUIDITY_SEED},
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, LOCKED_LIQUIDITY_SEED, MIN_LIQUIDITY},
     errors::*,
-    state::{Amm, Pool},
+    events::PoolCreated,
+    state::{Amm, Observation, Pool},
 };
 
 impl<'info> InitializePool<'info> {
-    pub fn initialize_pool(&mut self) -> Result<()> {
-        let pool = &mut self.pool;
-        pool.amm = self.amm.key();
-        pool.mint_a = self.mint_a.key();
-        pool.mint_b = self.mint_b.key();
+    pub fn initialize_pool(
+        &mut self,
+        fee_bps: Option<u16>,
+        bumps: &InitializePoolBumps,
+    ) -> Result<()> {
+        validate_mint_extensions(&self.mint_a.to_account_info())?;
+        validate_mint_extensions(&self.mint_b.to_account_info())?;
 
-        Ok(())
+        initialize_pool_state(
+            &mut self.pool,
+            &self.amm,
+            self.mint_a.key(),
+            self.mint_b.key(),
+            fee_bps,
+        )?;
+
+        lock_minimum_liquidity(
+            &self.token_program,
+            &self.mint_liquidity,
+            &self.locked_liquidity,
+            &self.pool_authority,
+            &[&[
+                &self.amm.key().to_bytes(),
+                &self.mint_a.key().to_bytes(),
+                &self.mint_b.key().to_bytes(),
+                AUTHORITY_SEED.as_bytes(),
+                &[bumps.pool_authority],
+            ]],
+        )
+    }
+}
+/// Sets up a newly created pool and seeds its price oracle
+pub(crate) fn initialize_pool_state(
+    pool: &mut Account<Pool>,
+    amm: &Account<Amm>,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    fee_bps: Option<u16>,
+) -> Result<()> {
+    pool.amm = amm.key();
+    pool.mint_a = mint_a;
+    pool.mint_b = mint_b;
+    pool.fee_bps = fee_bps;
+    pool.flash_fee_bps = pool.effective_fee(amm);
+
+    // Seed the price oracle with an empty observation
+    let timestamp = Clock::get()?.unix_timestamp;
+    pool.last_update_timestamp = timestamp;
+    pool.observations[0] = Observation {
+        timestamp,
+        ..Default::default()
+    };
+
+    emit!(PoolCreated {
+        amm: pool.amm,
+        pool: pool.key(),
+        mint_a: pool.mint_a,
+        mint_b: pool.mint_b,
+        fee_bps,
+    });
+
+    Ok(())
+}
+
+/// Mints the minimum liquidity to the pool locked liquidity account, which the
+/// program never transfers from, so that the liquidity supply never drops to zero
+pub(crate) fn lock_minimum_liquidity<'info>(
+    token_program: &Program<'info, Token>,
+    mint_liquidity: &InterfaceAccount<'info, Mint>,
+    locked_liquidity: &InterfaceAccount<'info, TokenAccount>,
+    pool_authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: mint_liquidity.to_account_info(),
+                to: locked_liquidity.to_account_info(),
+                authority: pool_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        MIN_LIQUIDITY,
+    )
+}
+
+/// Rejects Token-2022 mints carrying extensions that would let third parties
+/// move or lock pool funds, or that require extra accounts on transfer
+pub(crate) fn validate_mint_extensions(mint: &AccountInfo) -> Result<()> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(());
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in mint.get_extension_types()? {
+        match extension {
+            ExtensionType::PermanentDelegate
+            | ExtensionType::NonTransferable
+            | ExtensionType::TransferHook
+            | ExtensionType::ConfidentialTransferMint
+            | ExtensionType::DefaultAccountState
+            | ExtensionType::MintCloseAuthority => {
+                return err!(CustomError::UnsupportedMintExtension);
+            }
+            _ => {}
+        }
     }
+
+    Ok(())
 }
 #[derive(Accounts)]
+#[instruction(fee_bps: Option<u16>)]
 pub struct InitializePool<'info> {
     #[account(
         seeds = [
@@ -40,9 +150,10 @@ pub struct InitializePool<'info> {
             mint_b.key().as_ref(),
         ],
         bump,
-        constraint = mint_a.key() != mint_b.key() @ CustomError::InvalidTokenMint
+        constraint = mint_a.key() != mint_b.key() @ CustomError::InvalidTokenMint,
+        constraint = fee_bps.unwrap_or_default() <= amm.max_pool_fee @ CustomError::InvalidFee,
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: Box<Account<'info, Pool>>,
 
     /// CHECK: Read only authority
     #[account(
@@ -68,28 +179,49 @@ pub struct InitializePool<'info> {
         bump,
         mint::decimals = 6,
         mint::authority = pool_authority,
+        mint::token_program = token_program,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Liquidity account holding the permanently locked minimum liquidity
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            LOCKED_LIQUIDITY_SEED.as_ref(),
+        ],
+        bump,
+        token::mint = mint_liquidity,
+        token::authority = pool_authority,
     )]
-    pub mint_liquidity: Box<Account<'info, Mint>>,
+    pub locked_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    pub mint_a: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
 
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         init,
         payer = payer,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
     )]
-    pub pool_account_a: Box<Account<'info, TokenAccount>>,
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init,
         payer = payer,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
     )]
-    pub pool_account_b: Box<Account<'info, TokenAccount>>,
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Payer
     #[account(mut)]
@@ -97,6 +229,8 @@ pub struct InitializePool<'info> {
 
     /// Solana accounts
     pub token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
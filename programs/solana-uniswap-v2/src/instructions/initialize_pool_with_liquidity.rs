@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, LOCKED_LIQUIDITY_SEED, MIN_LIQUIDITY},
+    errors::*,
+    events::LiquidityAdded,
+    instructions::{initialize_pool_state, lock_minimum_liquidity, validate_mint_extensions},
+    math::liquidity_for_amounts,
+    state::{Amm, Pool},
+    token::{transfer_fee, wrap_sol},
+    utils::check_expiry,
+};
+
+impl<'info> InitializePoolWithLiquidity<'info> {
+    pub fn initialize_pool_with_liquidity(
+        &mut self,
+        fee_bps: Option<u16>,
+        amount_a: u64,
+        amount_b: u64,
+        min_liquidity_out: u64,
+        expiry_unix_ts: i64,
+        bumps: &InitializePoolWithLiquidityBumps,
+    ) -> Result<()> {
+        check_expiry(expiry_unix_ts)?;
+
+        validate_mint_extensions(&self.mint_a.to_account_info())?;
+        validate_mint_extensions(&self.mint_b.to_account_info())?;
+
+        initialize_pool_state(
+            &mut self.pool,
+            &self.amm,
+            self.mint_a.key(),
+            self.mint_b.key(),
+            fee_bps,
+        )?;
+
+        // Wrap the missing lamports when depositing native SOL
+        wrap_sol(
+            &self.depositor,
+            &mut self.depositor_account_a,
+            &self.token_program_a,
+            &self.system_program,
+            amount_a,
+        )?;
+        wrap_sol(
+            &self.depositor,
+            &mut self.depositor_account_b,
+            &self.token_program_b,
+            &self.system_program,
+            amount_b,
+        )?;
+
+        // Prevent depositing assets the depositor does not own
+        let amount_a = if amount_a > self.depositor_account_a.amount {
+            self.depositor_account_a.amount
+        } else {
+            amount_a
+        };
+        let amount_b = if amount_b > self.depositor_account_b.amount {
+            self.depositor_account_b.amount
+        } else {
+            amount_b
+        };
+
+        // Seeding deposit sets the initial price in the same instruction that
+        // creates the pool, so it can not be frontrun
+        let received_a = amount_a - transfer_fee(&self.mint_a.to_account_info(), amount_a)?;
+        let received_b = amount_b - transfer_fee(&self.mint_b.to_account_info(), amount_b)?;
+        let liquidity = liquidity_for_amounts(received_a, received_b);
+
+        // Lock some minimum liquidity out of the first deposit
+        if liquidity < MIN_LIQUIDITY {
+            return err!(CustomError::InsufficientDepositAmount);
+        }
+        let liquidity = liquidity - MIN_LIQUIDITY;
+
+        // Ensure minimum liquidity requirement is met
+        if liquidity < min_liquidity_out {
+            return err!(CustomError::LiquidityResultUnderflow);
+        }
+
+        // Transfer tokens to the pool
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program_a.to_account_info(),
+                TransferChecked {
+                    from: self.depositor_account_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
+                    to: self.pool_account_a.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            amount_a,
+            self.mint_a.decimals,
+        )?;
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program_b.to_account_info(),
+                TransferChecked {
+                    from: self.depositor_account_b.to_account_info(),
+                    mint: self.mint_b.to_account_info(),
+                    to: self.pool_account_b.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            amount_b,
+            self.mint_b.decimals,
+        )?;
+
+        // Mint the locked minimum liquidity and the rest to user
+        let authority_bump = bumps.pool_authority;
+        let authority_seeds = &[
+            &self.amm.key().to_bytes(),
+            &self.mint_a.key().to_bytes(),
+            &self.mint_b.key().to_bytes(),
+            AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+        lock_minimum_liquidity(
+            &self.token_program,
+            &self.mint_liquidity,
+            &self.locked_liquidity,
+            &self.pool_authority,
+            signer_seeds,
+        )?;
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.mint_liquidity.to_account_info(),
+                    to: self.depositor_account_liquidity.to_account_info(),
+                    authority: self.pool_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            liquidity,
+        )?;
+
+        // Move the reserves by the transferred amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        // Checkpoint reserves product for the next protocol fee computation
+        self.pool
+            .checkpoint_k_last(self.amm.fee_to != Pubkey::default());
+
+        emit!(LiquidityAdded {
+            pool: self.pool.key(),
+            user: self.depositor.key(),
+            amount_a,
+            amount_b,
+            liquidity,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+#[instruction(fee_bps: Option<u16>)]
+pub struct InitializePoolWithLiquidity<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        constraint = !amm.paused @ CustomError::Paused,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Pool::LEN,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+        ],
+        bump,
+        constraint = mint_a.key() != mint_b.key() @ CustomError::InvalidTokenMint,
+        constraint = fee_bps.unwrap_or_default() <= amm.max_pool_fee @ CustomError::InvalidFee,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    /// The account providing the seeding liquidity, depositing native SOL directly
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            LIQUIDITY_SEED.as_ref(),
+        ],
+        bump,
+        mint::decimals = 6,
+        mint::authority = pool_authority,
+        mint::token_program = token_program,
+    )]
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Liquidity account holding the permanently locked minimum liquidity
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            amm.key().as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            LOCKED_LIQUIDITY_SEED.as_ref(),
+        ],
+        bump,
+        token::mint = mint_liquidity,
+        token::authority = pool_authority,
+    )]
+    pub locked_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = depositor,
+    )]
+    pub depositor_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = depositor,
+        associated_token::token_program = token_program_a,
+    )]
+    pub depositor_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = depositor,
+        associated_token::token_program = token_program_b,
+    )]
+    pub depositor_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Solana accounts
+    pub token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -3,9 +3,43 @@ mod initialize_pool;
 mod deposit_liquidity;
 mod withdraw_liquidity;
 mod swap;
+mod swap_route;
+mod observe;
+mod set_fee_to;
+mod set_fee;
+mod propose_admin;
+mod accept_admin;
+mod renounce_admin;
+mod set_max_pool_fee;
+mod set_pool_fee;
+mod set_paused;
+mod flash_swap;
+mod flash_borrow;
+mod flash_repay;
+mod set_flash_fee;
+mod initialize_pool_with_liquidity;
+mod sync;
+mod skim;
 
 pub use initialize_amm::*;
 pub use initialize_pool::*;
 pub use deposit_liquidity::*;
 pub use withdraw_liquidity::*;
-pub use swap::*;
\ No newline at end of file
+pub use swap::*;
+pub use swap_route::*;
+pub use observe::*;
+pub use set_fee_to::*;
+pub use set_fee::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use renounce_admin::*;
+pub use set_max_pool_fee::*;
+pub use set_pool_fee::*;
+pub use set_paused::*;
+pub use flash_swap::*;
+pub use flash_borrow::*;
+pub use flash_repay::*;
+pub use set_flash_fee::*;
+pub use initialize_pool_with_liquidity::*;
+pub use sync::*;
+pub use skim::*;
\ No newline at end of file
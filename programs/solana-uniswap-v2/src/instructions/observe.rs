@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, state::Pool};
+
+impl<'info> Observe<'info> {
+    pub fn observe(&self, seconds_ago: u32) -> Result<Twap> {
+        let pool = &self.pool;
+        let end = pool.observations[pool.observation_index as usize];
+        let target = end.timestamp - seconds_ago as i64;
+
+        // Find the most recent earlier observation that covers the requested window
+        let start = pool
+            .observations
+            .iter()
+            .filter(|observation| {
+                observation.timestamp != 0
+                    && observation.timestamp < end.timestamp
+                    && observation.timestamp <= target
+            })
+            .max_by_key(|observation| observation.timestamp)
+            .copied()
+            .ok_or(CustomError::InsufficientObservations)?;
+
+        // Accumulators wrap, so differences are taken with wrapping arithmetic
+        let elapsed = (end.timestamp - start.timestamp) as u128;
+        Ok(Twap {
+            price_a: end
+                .price_a_cumulative
+                .wrapping_sub(start.price_a_cumulative)
+                / elapsed,
+            price_b: end
+                .price_b_cumulative
+                .wrapping_sub(start.price_b_cumulative)
+                / elapsed,
+            start_timestamp: start.timestamp,
+            end_timestamp: end.timestamp,
+        })
+    }
+}
+
+/// Time weighted average prices (UQ64.64) between two stored observations
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Twap {
+    pub price_a: u128,
+    pub price_b: u128,
+    pub start_timestamp: i64,
+    pub end_timestamp: i64,
+}
+#[derive(Accounts)]
+pub struct Observe<'info> {
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+}
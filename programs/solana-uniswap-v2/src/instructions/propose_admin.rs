@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AdminProposed, state::Amm};
+
+impl<'info> ProposeAdmin<'info> {
+    pub fn propose_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        // Transfer only completes once the new admin accepts it
+        let amm = &mut self.amm;
+        amm.pending_admin = new_admin;
+
+        emit!(AdminProposed {
+            amm: amm.key(),
+            admin: amm.admin,
+            pending_admin: new_admin,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
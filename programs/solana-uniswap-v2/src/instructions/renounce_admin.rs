@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{events::AdminRenounced, state::Amm};
+
+impl<'info> RenounceAdmin<'info> {
+    pub fn renounce_admin(&mut self) -> Result<()> {
+        // Leaves the AMM configuration permanently immutable
+        let amm = &mut self.amm;
+        amm.admin = Pubkey::default();
+        amm.pending_admin = Pubkey::default();
+
+        emit!(AdminRenounced {
+            amm: amm.key(),
+            admin: self.admin.key(),
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct RenounceAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
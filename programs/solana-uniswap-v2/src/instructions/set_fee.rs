@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::FeeUpdated, state::Amm};
+
+impl<'info> SetFee<'info> {
+    pub fn set_fee(&mut self, fee: u16) -> Result<()> {
+        let amm = &mut self.amm;
+        let old_fee = amm.fee;
+        amm.fee = fee;
+
+        emit!(FeeUpdated {
+            amm: amm.key(),
+            old_fee,
+            new_fee: fee,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+#[instruction(fee: u16)]
+pub struct SetFee<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+        constraint = fee < 10000 @ CustomError::InvalidFee,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::FeeToUpdated, state::Amm};
+
+impl<'info> SetFeeTo<'info> {
+    pub fn set_fee_to(&mut self, fee_to: Option<Pubkey>, protocol_fee_share: u8) -> Result<()> {
+        let amm = &mut self.amm;
+        let old_fee_to = Some(amm.fee_to).filter(|fee_to| *fee_to != Pubkey::default());
+        amm.fee_to = fee_to.unwrap_or_default();
+        amm.protocol_fee_share = protocol_fee_share;
+
+        emit!(FeeToUpdated {
+            amm: amm.key(),
+            old_fee_to,
+            new_fee_to: fee_to,
+            protocol_fee_share,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+#[instruction(fee_to: Option<Pubkey>, protocol_fee_share: u8)]
+pub struct SetFeeTo<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+        // A share of 1 would hand the protocol every liquidity provider fee
+        constraint = fee_to.is_none() || protocol_fee_share >= 2 @ CustomError::InvalidFee,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::*,
+    events::FlashFeeUpdated,
+    state::{Amm, Pool},
+};
+
+impl<'info> SetFlashFee<'info> {
+    pub fn set_flash_fee(&mut self, flash_fee_bps: u16) -> Result<()> {
+        let pool = &mut self.pool;
+        let old_flash_fee_bps = pool.flash_fee_bps;
+        pool.flash_fee_bps = flash_fee_bps;
+
+        emit!(FlashFeeUpdated {
+            pool: pool.key(),
+            old_flash_fee_bps,
+            new_flash_fee_bps: flash_fee_bps,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+#[instruction(flash_fee_bps: u16)]
+pub struct SetFlashFee<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        constraint = flash_fee_bps < 10000 @ CustomError::InvalidFee,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
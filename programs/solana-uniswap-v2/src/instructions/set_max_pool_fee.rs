@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, events::MaxPoolFeeUpdated, state::Amm};
+
+impl<'info> SetMaxPoolFee<'info> {
+    pub fn set_max_pool_fee(&mut self, max_pool_fee: u16) -> Result<()> {
+        // Existing pool overrides above the new cap are left untouched
+        let amm = &mut self.amm;
+        let old_max_pool_fee = amm.max_pool_fee;
+        amm.max_pool_fee = max_pool_fee;
+
+        emit!(MaxPoolFeeUpdated {
+            amm: amm.key(),
+            old_max_pool_fee,
+            new_max_pool_fee: max_pool_fee,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+#[instruction(max_pool_fee: u16)]
+pub struct SetMaxPoolFee<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+        constraint = max_pool_fee < 10000 @ CustomError::InvalidFee,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::{Paused, Unpaused},
+    state::{Amm, Pool},
+};
+
+impl<'info> SetPaused<'info> {
+    pub fn pause(&mut self, allow_withdrawals: bool) -> Result<()> {
+        // Pause the given pool only, or every pool of the AMM otherwise
+        match &mut self.pool {
+            Some(pool) => {
+                pool.paused = true;
+                pool.withdrawals_paused = !allow_withdrawals;
+            }
+            None => {
+                self.amm.paused = true;
+                self.amm.withdrawals_paused = !allow_withdrawals;
+            }
+        }
+
+        emit!(Paused {
+            amm: self.amm.key(),
+            pool: self.pool.as_ref().map(|pool| pool.key()),
+            allow_withdrawals,
+        });
+
+        Ok(())
+    }
+
+    pub fn unpause(&mut self) -> Result<()> {
+        match &mut self.pool {
+            Some(pool) => {
+                pool.paused = false;
+                pool.withdrawals_paused = false;
+            }
+            None => {
+                self.amm.paused = false;
+                self.amm.withdrawals_paused = false;
+            }
+        }
+
+        emit!(Unpaused {
+            amm: self.amm.key(),
+            pool: self.pool.as_ref().map(|pool| pool.key()),
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// Pool to pause, the whole AMM is paused when omitted
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+    )]
+    pub pool: Option<Box<Account<'info, Pool>>>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
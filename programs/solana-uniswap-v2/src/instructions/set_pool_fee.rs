@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::*,
+    events::PoolFeeUpdated,
+    state::{Amm, Pool},
+};
+
+impl<'info> SetPoolFee<'info> {
+    pub fn set_pool_fee(&mut self, fee_bps: Option<u16>) -> Result<()> {
+        let pool = &mut self.pool;
+        let old_fee_bps = pool.fee_bps;
+        pool.fee_bps = fee_bps;
+
+        emit!(PoolFeeUpdated {
+            pool: pool.key(),
+            old_fee_bps,
+            new_fee_bps: fee_bps,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+#[instruction(fee_bps: Option<u16>)]
+pub struct SetPoolFee<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        has_one = admin,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        constraint = fee_bps.unwrap_or_default() <= amm.max_pool_fee @ CustomError::InvalidFee,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Admin authority
+    pub admin: Signer<'info>,
+}
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::{
+    constants::AUTHORITY_SEED,
+    errors::*,
+    events::Skimmed,
+    state::{Amm, Pool},
+};
+
+impl<'info> Skim<'info> {
+    pub fn skim(&mut self, bumps: &SkimBumps) -> Result<()> {
+        // Everything held above the reserves was sent directly to the pool
+        let amount_a = self
+            .pool_account_a
+            .amount
+            .saturating_sub(self.pool.reserve_a);
+        let amount_b = self
+            .pool_account_b
+            .amount
+            .saturating_sub(self.pool.reserve_b);
+
+        // Transfer the excess tokens to the recipient
+        let authority_bump = bumps.pool_authority;
+        let authority_seeds = &[
+            &self.pool.amm.to_bytes(),
+            &self.mint_a.key().to_bytes(),
+            &self.mint_b.key().to_bytes(),
+            AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+        if amount_a > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
+                        to: self.recipient_account_a.to_account_info(),
+                        authority: self.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount_a,
+                self.mint_a.decimals,
+            )?;
+        }
+        if amount_b > 0 {
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
+                        to: self.recipient_account_b.to_account_info(),
+                        authority: self.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount_b,
+                self.mint_b.decimals,
+            )?;
+        }
+
+        emit!(Skimmed {
+            pool: self.pool.key(),
+            recipient_a: self.recipient_account_a.key(),
+            recipient_b: self.recipient_account_b.key(),
+            amount_a,
+            amount_b,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct Skim<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives the tokens held above the reserves
+    #[account(
+        mut,
+        token::mint = mint_a,
+        token::token_program = token_program_a,
+    )]
+    pub recipient_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::token_program = token_program_b,
+    )]
+    pub recipient_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Solana accounts
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+}
@@ -1,14 +1,21 @@
-use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
-};
-use fixed::types::I64F64;
-
 use crate::{
     constants::AUTHORITY_SEED,
     errors::*,
+    events::Swapped,
+    math::{
+        amount_after_fee, constant_product_input, constant_product_output, mul_div_ceil,
+        verify_invariant,
+    },
     state::{Amm, Pool},
+    token::{
+        is_native_mint, is_temporary_wsol, transfer_fee, transfer_inverse_fee, unwrap_sol, wrap_sol,
+    },
+    utils::check_expiry,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 impl<'info> Swap<'info> {
     pub fn swap(
@@ -16,56 +23,145 @@ impl<'info> Swap<'info> {
         swap_a: bool,
         input_amount: u64,
         min_result_amount: u64,
+        expiry_unix_ts: i64,
         bumps: &SwapBumps,
     ) -> Result<()> {
-        // Ensure owned assets are only being deposited
-        let input = if swap_a && input_amount > self.trader_account_a.amount {
-            self.trader_account_a.amount
-        } else if !swap_a && input_amount > self.trader_account_b.amount {
-            self.trader_account_b.amount
+        check_expiry(expiry_unix_ts)?;
+
+        // Ensure owned assets are only being deposited, native SOL is wrapped
+        // from the trader's lamports instead
+        let account_in = if swap_a {
+            &self.trader_account_a
         } else {
+            &self.trader_account_b
+        };
+        let input = if is_native_mint(&account_in.mint) {
             input_amount
+        } else {
+            input_amount.min(account_in.amount)
+        };
+
+        // Only the amount credited to the pool after transfer fees is priced
+        let (mint_in, mint_out) = if swap_a {
+            (&self.mint_a, &self.mint_b)
+        } else {
+            (&self.mint_b, &self.mint_a)
         };
+        let received = input - transfer_fee(&mint_in.to_account_info(), input)?;
 
         // Calculate effective input after deducting fee
-        let amm = &self.amm;
-        let taxed_input = input - input * amm.fee as u64 / 10000;
+        let fee = self.pool.effective_fee(&self.amm);
+        let taxed_input = amount_after_fee(received, fee)?;
 
-        let pool_a = &self.pool_account_a;
-        let pool_b = &self.pool_account_b;
         // Compute swap result based on constant product formula
+        let (reserve_a, reserve_b) = (self.pool.reserve_a, self.pool.reserve_b);
         let result = if swap_a {
-            I64F64::from_num(taxed_input)
-                .checked_mul(I64F64::from_num(pool_b.amount))
-                .unwrap()
-                .checked_div(
-                    I64F64::from_num(pool_a.amount)
-                        .checked_add(I64F64::from_num(taxed_input))
-                        .unwrap(),
-                )
-                .unwrap()
+            constant_product_output(taxed_input, reserve_a, reserve_b)?
         } else {
-            I64F64::from_num(taxed_input)
-                .checked_mul(I64F64::from_num(pool_a.amount))
-                .unwrap()
-                .checked_div(
-                    I64F64::from_num(pool_b.amount)
-                        .checked_add(I64F64::from_num(taxed_input))
-                        .unwrap(),
-                )
-                .unwrap()
-        }
-        .to_num::<u64>();
+            constant_product_output(taxed_input, reserve_b, reserve_a)?
+        };
 
-        // Ensure minimum output requirement is met
-        if result < min_result_amount {
+        // Ensure minimum output requirement is met by what the trader receives
+        let received_result = result - transfer_fee(&mint_out.to_account_info(), result)?;
+        if received_result < min_result_amount {
             return err!(CustomError::SwapResultUnderflow);
         }
 
-        // Calculate pre-swap invariant for later verification
-        let invariant = pool_a.amount * pool_b.amount;
+        self.execute(swap_a, input, result, received, taxed_input, bumps)
+    }
+
+    pub fn swap_exact_out(
+        &mut self,
+        swap_a: bool,
+        output_amount: u64,
+        max_input_amount: u64,
+        expiry_unix_ts: i64,
+        bumps: &SwapBumps,
+    ) -> Result<()> {
+        check_expiry(expiry_unix_ts)?;
+
+        let (reserve_a, reserve_b) = (self.pool.reserve_a, self.pool.reserve_b);
+        let (reserve_in, reserve_out) = if swap_a {
+            (reserve_a, reserve_b)
+        } else {
+            (reserve_b, reserve_a)
+        };
+
+        // Gross up the output so the trader receives it net of transfer fees
+        let (mint_in, mint_out) = if swap_a {
+            (&self.mint_a, &self.mint_b)
+        } else {
+            (&self.mint_b, &self.mint_a)
+        };
+        let gross_output = output_amount
+            .checked_add(transfer_inverse_fee(
+                &mint_out.to_account_info(),
+                output_amount,
+            )?)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // Pool can never be fully drained of the output token
+        if output_amount == 0 || gross_output >= reserve_out {
+            return err!(CustomError::InsufficientLiquidity);
+        }
+
+        // Compute required input based on inverted constant product formula,
+        // rounding up at every step so the trader always covers the output
+        let fee = self.pool.effective_fee(&self.amm);
+        let taxed_input = constant_product_input(gross_output, reserve_in, reserve_out)?;
+        let received = mul_div_ceil(taxed_input, 10000, 10000 - fee as u64)?;
+        let input = received
+            .checked_add(transfer_inverse_fee(&mint_in.to_account_info(), received)?)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // Ensure maximum input requirement is met
+        if input > max_input_amount {
+            return err!(CustomError::SwapInputOverflow);
+        }
+
+        self.execute(swap_a, input, gross_output, received, taxed_input, bumps)
+    }
+
+    /// Trades `input` for `output` once priced, `received` being the input
+    /// credited to the pool and `taxed_input` what remains of it after the fee
+    fn execute(
+        &mut self,
+        swap_a: bool,
+        input: u64,
+        output: u64,
+        received: u64,
+        taxed_input: u64,
+        bumps: &SwapBumps,
+    ) -> Result<()> {
+        // Wrap the missing lamports when paying with native SOL
+        wrap_sol(
+            &self.trader,
+            if swap_a {
+                &mut self.trader_account_a
+            } else {
+                &mut self.trader_account_b
+            },
+            if swap_a {
+                &self.token_program_a
+            } else {
+                &self.token_program_b
+            },
+            &self.system_program,
+            input,
+        )?;
+
+        // Native SOL received in a fresh wrapped account is returned as lamports
+        let unwrap_output = is_temporary_wsol(if swap_a {
+            &self.trader_account_b
+        } else {
+            &self.trader_account_a
+        });
+
+        // Update price oracle with pre-swap reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
 
         // Transfer tokens to the pool
+        let (reserve_a, reserve_b) = (self.pool.reserve_a, self.pool.reserve_b);
         let authority_bump = bumps.pool_authority;
         let authority_seeds = &[
             &self.pool.amm.to_bytes(),
@@ -76,52 +172,60 @@ impl<'info> Swap<'info> {
         ];
         let signer_seeds = &[&authority_seeds[..]];
         if swap_a {
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new(
-                    self.token_program.to_account_info(),
-                    Transfer {
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
                         from: self.trader_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
                         to: self.pool_account_a.to_account_info(),
                         authority: self.trader.to_account_info(),
                     },
                 ),
                 input,
+                self.mint_a.decimals,
             )?;
-            token::transfer(
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    Transfer {
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
                         from: self.pool_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
                         to: self.trader_account_b.to_account_info(),
                         authority: self.pool_authority.to_account_info(),
                     },
                     signer_seeds,
                 ),
-                result,
+                output,
+                self.mint_b.decimals,
             )?;
         } else {
-            token::transfer(
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    self.token_program_b.to_account_info(),
+                    TransferChecked {
+                        from: self.trader_account_b.to_account_info(),
+                        mint: self.mint_b.to_account_info(),
+                        to: self.pool_account_b.to_account_info(),
+                        authority: self.trader.to_account_info(),
+                    },
+                ),
+                input,
+                self.mint_b.decimals,
+            )?;
+            token_interface::transfer_checked(
                 CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    Transfer {
+                    self.token_program_a.to_account_info(),
+                    TransferChecked {
                         from: self.pool_account_a.to_account_info(),
+                        mint: self.mint_a.to_account_info(),
                         to: self.trader_account_a.to_account_info(),
                         authority: self.pool_authority.to_account_info(),
                     },
                     signer_seeds,
                 ),
-                input,
-            )?;
-            token::transfer(
-                CpiContext::new(
-                    self.token_program.to_account_info(),
-                    Transfer {
-                        from: self.trader_account_b.to_account_info(),
-                        to: self.pool_account_b.to_account_info(),
-                        authority: self.trader.to_account_info(),
-                    },
-                ),
-                result,
+                output,
+                self.mint_a.decimals,
             )?;
         }
 
@@ -129,17 +233,51 @@ impl<'info> Swap<'info> {
             "Traded {} tokens ({} after fees) for {}",
             input,
             taxed_input,
-            result
+            output
         );
 
-        // Verify post-swap invariant to ensure pool integrity
-        // Reload accounts because of the CPIs
-        self.pool_account_a.reload()?;
-        self.pool_account_b.reload()?;
-        if invariant > self.pool_account_a.amount * self.pool_account_a.amount {
-            return err!(CustomError::InvariantError);
+        // Verify the fee-adjusted post-swap invariant to ensure pool integrity
+        // Reload accounts because of the CPIs and move the reserves accordingly
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+        let (amount_a_out, amount_b_out) = if swap_a { (0, output) } else { (output, 0) };
+        verify_invariant(
+            reserve_a,
+            reserve_b,
+            self.pool.reserve_a,
+            self.pool.reserve_b,
+            self.pool.reserve_a.saturating_sub(reserve_a - amount_a_out),
+            self.pool.reserve_b.saturating_sub(reserve_b - amount_b_out),
+            self.pool.effective_fee(&self.amm),
+        )?;
+
+        if unwrap_output {
+            unwrap_sol(
+                &self.trader,
+                if swap_a {
+                    &self.trader_account_b
+                } else {
+                    &self.trader_account_a
+                },
+                if swap_a {
+                    &self.token_program_b
+                } else {
+                    &self.token_program_a
+                },
+            )?;
         }
 
+        emit!(Swapped {
+            pool: self.pool.key(),
+            user: self.trader.key(),
+            swap_a,
+            amount_in: input,
+            amount_out: output,
+            fee_paid: received - taxed_input,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
         Ok(())
     }
 }
@@ -150,10 +288,12 @@ pub struct Swap<'info> {
             amm.id.as_ref()
         ],
         bump,
+        constraint = !amm.paused @ CustomError::Paused,
     )]
     pub amm: Account<'info, Amm>,
 
     #[account(
+        mut,
         seeds = [
             pool.amm.as_ref(),
             pool.mint_a.key().as_ref(),
@@ -163,8 +303,10 @@ pub struct Swap<'info> {
         has_one = amm,
         has_one = mint_a,
         has_one = mint_b,
+        constraint = !pool.paused @ CustomError::Paused,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: Box<Account<'info, Pool>>,
 
     /// CHECK: Read only authority
     #[account(
@@ -178,49 +320,57 @@ pub struct Swap<'info> {
     )]
     pub pool_authority: AccountInfo<'info>,
 
-    /// The account doing the swap
+    /// The account doing the swap, paying and receiving native SOL directly
+    #[account(mut)]
     pub trader: Signer<'info>,
 
-    pub mint_a: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
 
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
     )]
-    pub pool_account_a: Box<Account<'info, TokenAccount>>,
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
     )]
-    pub pool_account_b: Box<Account<'info, TokenAccount>>,
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint_a,
         associated_token::authority = trader,
+        associated_token::token_program = token_program_a,
     )]
-    pub trader_account_a: Box<Account<'info, TokenAccount>>,
+    pub trader_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint_b,
         associated_token::authority = trader,
+        associated_token::token_program = token_program_b,
     )]
-    pub trader_account_b: Box<Account<'info, TokenAccount>>,
+    pub trader_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Payer
     #[account(mut)]
     pub payer: Signer<'info>,
 
     /// Solana accounts
-    pub token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,303 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    constants::AUTHORITY_SEED,
+    errors::*,
+    events::Swapped,
+    math::{amount_after_fee, constant_product_output, verify_invariant},
+    state::{Amm, Pool},
+    token::transfer_fee,
+    utils::check_expiry,
+};
+
+/// Accounts expected in `remaining_accounts` for every hop of the route:
+/// pool, pool authority, pool input token account, pool output token account,
+/// output mint and output token program
+pub const ROUTE_HOP_ACCOUNTS: usize = 6;
+
+struct RouteHop<'info> {
+    pool: Account<'info, Pool>,
+    swap_a: bool,
+    authority_bump: u8,
+    pool_authority: &'info AccountInfo<'info>,
+    pool_account_in: Box<InterfaceAccount<'info, TokenAccount>>,
+    pool_account_out: Box<InterfaceAccount<'info, TokenAccount>>,
+    mint_out: Box<InterfaceAccount<'info, Mint>>,
+    token_program_out: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SwapRoute<'info> {
+    pub fn swap_route(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        input_amount: u64,
+        min_result_amount: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        check_expiry(expiry_unix_ts)?;
+
+        let hop_accounts = remaining_accounts.chunks_exact(ROUTE_HOP_ACCOUNTS);
+        if remaining_accounts.is_empty() || !hop_accounts.remainder().is_empty() {
+            return err!(CustomError::InvalidRoute);
+        }
+
+        // Validate every hop upfront and link the mints along the route
+        let mut hops = Vec::with_capacity(hop_accounts.len());
+        let mut current_mint = self.mint_in.key();
+        let mut current_token_program = self.token_program_in.key();
+        for accounts in hop_accounts {
+            let pool = Account::<Pool>::try_from(&accounts[0])?;
+            if pool.paused {
+                return err!(CustomError::Paused);
+            }
+            if pool.flash_locked {
+                return err!(CustomError::FlashLocked);
+            }
+            if pool.amm != self.amm.key()
+                || hops
+                    .iter()
+                    .any(|hop: &RouteHop| hop.pool.key() == pool.key())
+            {
+                return err!(CustomError::InvalidRoute);
+            }
+
+            let next_mint = if current_mint == pool.mint_a {
+                pool.mint_b
+            } else if current_mint == pool.mint_b {
+                pool.mint_a
+            } else {
+                return err!(CustomError::InvalidTokenMint);
+            };
+
+            let mint_out = Box::new(InterfaceAccount::<Mint>::try_from(&accounts[4])?);
+            let token_program_out = Interface::<TokenInterface>::try_from(&accounts[5])?;
+            if mint_out.key() != next_mint
+                || mint_out.to_account_info().owner != &token_program_out.key()
+            {
+                return err!(CustomError::InvalidTokenMint);
+            }
+
+            let (pool_authority, authority_bump) = Pubkey::find_program_address(
+                &[
+                    pool.amm.as_ref(),
+                    pool.mint_a.as_ref(),
+                    pool.mint_b.as_ref(),
+                    AUTHORITY_SEED.as_ref(),
+                ],
+                &crate::ID,
+            );
+            if accounts[1].key() != pool_authority
+                || accounts[2].key()
+                    != get_associated_token_address_with_program_id(
+                        &pool_authority,
+                        &current_mint,
+                        &current_token_program,
+                    )
+                || accounts[3].key()
+                    != get_associated_token_address_with_program_id(
+                        &pool_authority,
+                        &next_mint,
+                        &token_program_out.key(),
+                    )
+            {
+                return err!(CustomError::InvalidRoute);
+            }
+
+            hops.push(RouteHop {
+                swap_a: current_mint == pool.mint_a,
+                pool,
+                authority_bump,
+                pool_authority: &accounts[1],
+                pool_account_in: Box::new(InterfaceAccount::try_from(&accounts[2])?),
+                pool_account_out: Box::new(InterfaceAccount::try_from(&accounts[3])?),
+                mint_out,
+                token_program_out,
+            });
+            current_mint = next_mint;
+            current_token_program = accounts[5].key();
+        }
+        if current_mint != self.mint_out.key()
+            || current_token_program != self.token_program_out.key()
+        {
+            return err!(CustomError::InvalidTokenMint);
+        }
+
+        // Ensure owned assets are only being deposited
+        let input = if input_amount > self.trader_account_in.amount {
+            self.trader_account_in.amount
+        } else {
+            input_amount
+        };
+
+        // Transfer tokens to the first pool of the route
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program_in.to_account_info(),
+                TransferChecked {
+                    from: self.trader_account_in.to_account_info(),
+                    mint: self.mint_in.to_account_info(),
+                    to: hops[0].pool_account_in.to_account_info(),
+                    authority: self.trader.to_account_info(),
+                },
+            ),
+            input,
+            self.mint_in.decimals,
+        )?;
+
+        // Chain each hop output into the input of the next pool
+        let amm = &self.amm;
+        let timestamp = Clock::get()?.unix_timestamp;
+        let mut amount = input;
+        for i in 0..hops.len() {
+            let destination = match hops.get(i + 1) {
+                Some(next) => next.pool_account_in.to_account_info(),
+                None => self.trader_account_out.to_account_info(),
+            };
+            let mint_in = match i {
+                0 => self.mint_in.to_account_info(),
+                _ => hops[i - 1].mint_out.to_account_info(),
+            };
+            let hop = &mut hops[i];
+
+            // Price against the reserves tracked by the pool
+            let received = amount - transfer_fee(&mint_in, amount)?;
+            let (reserve_in, reserve_out) = if hop.swap_a {
+                (hop.pool.reserve_a, hop.pool.reserve_b)
+            } else {
+                (hop.pool.reserve_b, hop.pool.reserve_a)
+            };
+
+            // Calculate effective input after deducting fee
+            let fee = hop.pool.effective_fee(amm);
+            let taxed_input = amount_after_fee(received, fee)?;
+            let result = constant_product_output(taxed_input, reserve_in, reserve_out)?;
+
+            // Update price oracle with pre-swap reserves
+            hop.pool.update_oracle(timestamp);
+
+            let authority_seeds = &[
+                &hop.pool.amm.to_bytes(),
+                &hop.pool.mint_a.to_bytes(),
+                &hop.pool.mint_b.to_bytes(),
+                AUTHORITY_SEED.as_bytes(),
+                &[hop.authority_bump],
+            ];
+            let signer_seeds = &[&authority_seeds[..]];
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    hop.token_program_out.to_account_info(),
+                    TransferChecked {
+                        from: hop.pool_account_out.to_account_info(),
+                        mint: hop.mint_out.to_account_info(),
+                        to: destination,
+                        authority: hop.pool_authority.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                result,
+                hop.mint_out.decimals,
+            )?;
+
+            // Verify the fee-adjusted invariant of this hop
+            // Reload accounts because of the CPIs and move the reserves accordingly
+            let (reserve_a, reserve_b) = (hop.pool.reserve_a, hop.pool.reserve_b);
+            let (amount_a_out, amount_b_out) = if hop.swap_a {
+                hop.pool
+                    .move_reserves(&mut hop.pool_account_in, &mut hop.pool_account_out)?;
+                (0, result)
+            } else {
+                hop.pool
+                    .move_reserves(&mut hop.pool_account_out, &mut hop.pool_account_in)?;
+                (result, 0)
+            };
+            verify_invariant(
+                reserve_a,
+                reserve_b,
+                hop.pool.reserve_a,
+                hop.pool.reserve_b,
+                hop.pool.reserve_a.saturating_sub(reserve_a - amount_a_out),
+                hop.pool.reserve_b.saturating_sub(reserve_b - amount_b_out),
+                fee,
+            )?;
+            hop.pool.exit(&crate::ID)?;
+
+            msg!(
+                "Hop {}: traded {} tokens ({} after fees) for {}",
+                i,
+                amount,
+                taxed_input,
+                result
+            );
+            emit!(Swapped {
+                pool: hop.pool.key(),
+                user: self.trader.key(),
+                swap_a: hop.swap_a,
+                amount_in: amount,
+                amount_out: result,
+                fee_paid: received - taxed_input,
+                reserve_a: hop.pool.reserve_a,
+                reserve_b: hop.pool.reserve_b,
+            });
+            amount = result;
+        }
+
+        // Ensure minimum output requirement is met by what the trader receives
+        let mint_out = self.mint_out.to_account_info();
+        if amount - transfer_fee(&mint_out, amount)? < min_result_amount {
+            return err!(CustomError::SwapResultUnderflow);
+        }
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+        constraint = !amm.paused @ CustomError::Paused,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    /// The account doing the swap
+    pub trader: Signer<'info>,
+
+    #[account(mint::token_program = token_program_in)]
+    pub mint_in: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_out)]
+    pub mint_out: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = trader,
+        associated_token::token_program = token_program_in,
+    )]
+    pub trader_account_in: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_out,
+        associated_token::authority = trader,
+        associated_token::token_program = token_program_out,
+    )]
+    pub trader_account_out: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payer
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Solana accounts
+    pub token_program_in: Interface<'info, TokenInterface>,
+    pub token_program_out: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::AUTHORITY_SEED,
+    errors::*,
+    events::Synced,
+    state::{Amm, Pool},
+};
+
+impl<'info> SyncReserves<'info> {
+    pub fn sync(&mut self) -> Result<()> {
+        // Update price oracle with the reserves being replaced
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Count every token held by the pool accounts as reserves
+        self.pool.reserve_a = self.pool_account_a.amount;
+        self.pool.reserve_b = self.pool_account_b.amount;
+
+        emit!(Synced {
+            pool: self.pool.key(),
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
+        Ok(())
+    }
+}
+#[derive(Accounts)]
+pub struct SyncReserves<'info> {
+    #[account(
+        seeds = [
+            amm.id.as_ref()
+        ],
+        bump,
+    )]
+    pub amm: Account<'info, Amm>,
+
+    #[account(
+        mut,
+        seeds = [
+            pool.amm.as_ref(),
+            pool.mint_a.key().as_ref(),
+            pool.mint_b.key().as_ref(),
+        ],
+        bump,
+        has_one = amm,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// CHECK: Read only authority
+    #[account(
+        seeds = [
+            pool.amm.as_ref(),
+            mint_a.key().as_ref(),
+            mint_b.key().as_ref(),
+            AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_authority: AccountInfo<'info>,
+
+    #[account(mint::token_program = token_program_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mint::token_program = token_program_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        associated_token::mint = mint_a,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
+    )]
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        associated_token::mint = mint_b,
+        associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
+    )]
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Solana accounts
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+}
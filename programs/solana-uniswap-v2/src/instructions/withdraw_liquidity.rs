@@ -1,20 +1,30 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Burn, Mint, Token, TokenAccount, Transfer},
+    token::Token,
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
-use fixed::types::I64F64;
 
 use crate::{
-    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, MIN_LIQUIDITY},
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED},
+    errors::CustomError,
+    events::LiquidityRemoved,
+    math::{amount_after_fee, constant_product_output, mul_div},
     state::{Amm, Pool},
+    token::transfer_fee,
+    utils::check_expiry,
 };
 impl<'info> WithdrawLiquidity<'info> {
     pub fn withdraw_liquidity(
         &mut self,
         amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        expiry_unix_ts: i64,
         bumps: &WithdrawLiquidityBumps,
     ) -> Result<()> {
+        check_expiry(expiry_unix_ts)?;
+
         let authority_bump = bumps.pool_authority;
         let authority_seeds = &[
             &self.pool.amm.to_bytes(),
@@ -25,50 +35,202 @@ impl<'info> WithdrawLiquidity<'info> {
         ];
         let signer_seeds = &[&authority_seeds[..]];
 
-        // Calculate and transfer token A from pool to depositor
-        let amount_a = I64F64::from_num(amount)
-            .checked_mul(I64F64::from_num(self.pool_account_a.amount))
-            .unwrap()
-            .checked_div(I64F64::from_num(self.mint_liquidity.supply + MIN_LIQUIDITY))
-            .unwrap()
-            .floor()
-            .to_num::<u64>();
-        token::transfer(
+        // Update price oracle with pre-withdrawal reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Mint protocol share of the fees accrued since the last liquidity event
+        let fee_on = self.pool.mint_protocol_fee(
+            &self.amm,
+            &mut self.mint_liquidity,
+            &self.fee_to_account_liquidity,
+            &self.pool_authority,
+            &self.token_program,
+            signer_seeds,
+        )?;
+
+        // Calculate token A and B amounts owed to the depositor
+        let (amount_a, amount_b) = self.withdraw_amounts(amount)?;
+
+        // Ensure minimum withdrawal requirement is met by what the depositor receives
+        if amount_a - transfer_fee(&self.mint_a.to_account_info(), amount_a)? < min_amount_a
+            || amount_b - transfer_fee(&self.mint_b.to_account_info(), amount_b)? < min_amount_b
+        {
+            return err!(CustomError::LiquidityResultUnderflow);
+        }
+
+        // Transfer tokens from pool to depositor
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                Transfer {
+                self.token_program_a.to_account_info(),
+                TransferChecked {
                     from: self.pool_account_a.to_account_info(),
+                    mint: self.mint_a.to_account_info(),
                     to: self.depositor_account_a.to_account_info(),
                     authority: self.pool_authority.to_account_info(),
                 },
                 signer_seeds,
             ),
             amount_a,
+            self.mint_a.decimals,
         )?;
-
-        // Calculate and transfer token B from pool to depositor
-        let amount_b = I64F64::from_num(amount)
-            .checked_mul(I64F64::from_num(self.pool_account_b.amount))
-            .unwrap()
-            .checked_div(I64F64::from_num(self.mint_liquidity.supply + MIN_LIQUIDITY))
-            .unwrap()
-            .floor()
-            .to_num::<u64>();
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
-                self.token_program.to_account_info(),
-                Transfer {
+                self.token_program_b.to_account_info(),
+                TransferChecked {
                     from: self.pool_account_b.to_account_info(),
+                    mint: self.mint_b.to_account_info(),
                     to: self.depositor_account_b.to_account_info(),
                     authority: self.pool_authority.to_account_info(),
                 },
                 signer_seeds,
             ),
             amount_b,
+            self.mint_b.decimals,
+        )?;
+
+        // Burn liquidity tokens from depositor's account, reverted if amout is invalid
+        token_interface::burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.mint_liquidity.to_account_info(),
+                    from: self.depositor_account_liquidity.to_account_info(),
+                    authority: self.depositor.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // Move the reserves by the transferred amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        // Checkpoint reserves product for the next protocol fee computation
+        self.pool.checkpoint_k_last(fee_on);
+
+        emit!(LiquidityRemoved {
+            pool: self.pool.key(),
+            user: self.depositor.key(),
+            amount_a,
+            amount_b,
+            liquidity: amount,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
+        Ok(())
+    }
+
+    pub fn withdraw_single_sided(
+        &mut self,
+        receive_a: bool,
+        amount: u64,
+        min_amount_out: u64,
+        expiry_unix_ts: i64,
+        bumps: &WithdrawLiquidityBumps,
+    ) -> Result<()> {
+        check_expiry(expiry_unix_ts)?;
+
+        // Swapping the unwanted side is halted along with swaps
+        if self.amm.paused || self.pool.paused {
+            return err!(CustomError::Paused);
+        }
+
+        let authority_bump = bumps.pool_authority;
+        let authority_seeds = &[
+            &self.pool.amm.to_bytes(),
+            &self.mint_a.key().to_bytes(),
+            &self.mint_b.key().to_bytes(),
+            AUTHORITY_SEED.as_bytes(),
+            &[authority_bump],
+        ];
+        let signer_seeds = &[&authority_seeds[..]];
+
+        // Update price oracle with pre-withdrawal reserves
+        self.pool.update_oracle(Clock::get()?.unix_timestamp);
+
+        // Mint protocol share of the fees accrued since the last liquidity event
+        let fee_on = self.pool.mint_protocol_fee(
+            &self.amm,
+            &mut self.mint_liquidity,
+            &self.fee_to_account_liquidity,
+            &self.pool_authority,
+            &self.token_program,
+            signer_seeds,
+        )?;
+
+        // Calculate token A and B amounts owed to the depositor
+        let (amount_a, amount_b) = self.withdraw_amounts(amount)?;
+
+        // Swap the unwanted side back into the pool at post-withdrawal
+        // reserves, those tokens never leave the pool
+        // More liquidity than the supply would be owed more than the reserves
+        let remaining_a = self
+            .pool
+            .reserve_a
+            .checked_sub(amount_a)
+            .ok_or(CustomError::MathOverflow)?;
+        let remaining_b = self
+            .pool
+            .reserve_b
+            .checked_sub(amount_b)
+            .ok_or(CustomError::MathOverflow)?;
+        let (amount_keep, amount_swap, reserve_out, reserve_in) = if receive_a {
+            (amount_a, amount_b, remaining_a, remaining_b)
+        } else {
+            (amount_b, amount_a, remaining_b, remaining_a)
+        };
+        let fee = self.pool.effective_fee(&self.amm);
+        let taxed_swap = amount_after_fee(amount_swap, fee)?;
+        let amount_out = amount_keep
+            .checked_add(constant_product_output(
+                taxed_swap,
+                reserve_in,
+                reserve_out,
+            )?)
+            .ok_or(CustomError::MathOverflow)?;
+
+        // Ensure minimum withdrawal requirement is met by what the depositor receives
+        let (mint_out, decimals_out) = if receive_a {
+            (self.mint_a.to_account_info(), self.mint_a.decimals)
+        } else {
+            (self.mint_b.to_account_info(), self.mint_b.decimals)
+        };
+        if amount_out - transfer_fee(&mint_out, amount_out)? < min_amount_out {
+            return err!(CustomError::LiquidityResultUnderflow);
+        }
+
+        // Transfer the requested token from pool to depositor
+        let (token_program_out, pool_account_out, depositor_account_out) = if receive_a {
+            (
+                self.token_program_a.to_account_info(),
+                self.pool_account_a.to_account_info(),
+                self.depositor_account_a.to_account_info(),
+            )
+        } else {
+            (
+                self.token_program_b.to_account_info(),
+                self.pool_account_b.to_account_info(),
+                self.depositor_account_b.to_account_info(),
+            )
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program_out,
+                TransferChecked {
+                    from: pool_account_out,
+                    mint: mint_out,
+                    to: depositor_account_out,
+                    authority: self.pool_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_out,
+            decimals_out,
         )?;
 
         // Burn liquidity tokens from depositor's account, reverted if amout is invalid
-        token::burn(
+        token_interface::burn(
             CpiContext::new(
                 self.token_program.to_account_info(),
                 Burn {
@@ -80,8 +242,34 @@ impl<'info> WithdrawLiquidity<'info> {
             amount,
         )?;
 
+        // Move the reserves by the transferred amounts
+        self.pool
+            .move_reserves(&mut self.pool_account_a, &mut self.pool_account_b)?;
+
+        // Checkpoint reserves product for the next protocol fee computation
+        self.pool.checkpoint_k_last(fee_on);
+
+        emit!(LiquidityRemoved {
+            pool: self.pool.key(),
+            user: self.depositor.key(),
+            amount_a: if receive_a { amount_out } else { 0 },
+            amount_b: if receive_a { 0 } else { amount_out },
+            liquidity: amount,
+            reserve_a: self.pool.reserve_a,
+            reserve_b: self.pool.reserve_b,
+        });
+
         Ok(())
     }
+
+    /// Pro-rata token A and B amounts owed for burning `amount` liquidity tokens
+    fn withdraw_amounts(&self, amount: u64) -> Result<(u64, u64)> {
+        let supply = self.mint_liquidity.supply;
+        let amount_a = mul_div(amount, self.pool.reserve_a, supply)?;
+        let amount_b = mul_div(amount, self.pool.reserve_b, supply)?;
+
+        Ok((amount_a, amount_b))
+    }
 }
 #[derive(Accounts)]
 pub struct WithdrawLiquidity<'info> {
@@ -90,20 +278,25 @@ pub struct WithdrawLiquidity<'info> {
             amm.id.as_ref()
         ],
         bump,
+        constraint = !amm.withdrawals_paused @ CustomError::Paused,
     )]
     pub amm: Account<'info, Amm>,
 
     #[account(
+        mut,
         seeds = [
             pool.amm.as_ref(),
             pool.mint_a.key().as_ref(),
             pool.mint_b.key().as_ref(),
         ],
         bump,
+        has_one = amm,
         has_one = mint_a,
         has_one = mint_b,
+        constraint = !pool.withdrawals_paused @ CustomError::Paused,
+        constraint = !pool.flash_locked @ CustomError::FlashLocked,
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: Box<Account<'info, Pool>>,
 
     /// CHECK: Read only authority
     #[account(
@@ -130,27 +323,35 @@ pub struct WithdrawLiquidity<'info> {
         ],
         bump,
     )]
-    pub mint_liquidity: Box<Account<'info, Mint>>,
+    pub mint_liquidity: Box<InterfaceAccount<'info, Mint>>,
 
-    #[account(mut)]
-    pub mint_a: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        mint::token_program = token_program_a,
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
 
-    #[account(mut)]
-    pub mint_b: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        mint::token_program = token_program_b,
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
 
     #[account(
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_a,
     )]
-    pub pool_account_a: Box<Account<'info, TokenAccount>>,
+    pub pool_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         mut,
         associated_token::mint = mint_b,
         associated_token::authority = pool_authority,
+        associated_token::token_program = token_program_b,
     )]
-    pub pool_account_b: Box<Account<'info, TokenAccount>>,
+    pub pool_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
@@ -158,23 +359,39 @@ pub struct WithdrawLiquidity<'info> {
         associated_token::mint = mint_liquidity,
         associated_token::authority = depositor,
     )]
-    pub depositor_account_liquidity: Box<Account<'info, TokenAccount>>,
+    pub depositor_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint_a,
         associated_token::authority = depositor,
+        associated_token::token_program = token_program_a,
     )]
-    pub depositor_account_a: Box<Account<'info, TokenAccount>>,
+    pub depositor_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
         payer = payer,
         associated_token::mint = mint_b,
         associated_token::authority = depositor,
+        associated_token::token_program = token_program_b,
+    )]
+    pub depositor_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Protocol fee recipient, only used as the liquidity account owner
+    #[account(address = amm.fee_to @ CustomError::InvalidFeeRecipient)]
+    pub fee_to: AccountInfo<'info>,
+
+    /// Protocol fee recipient liquidity account, created when first needed so
+    /// that minting the protocol fee never blocks liquidity providers
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_liquidity,
+        associated_token::authority = fee_to,
     )]
-    pub depositor_account_b: Box<Account<'info, TokenAccount>>,
+    pub fee_to_account_liquidity: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// Payer
     #[account(mut)]
@@ -182,6 +399,8 @@ pub struct WithdrawLiquidity<'info> {
 
     /// Solana accounts
     pub token_program: Program<'info, Token>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
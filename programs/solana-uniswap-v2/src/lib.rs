@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 
-mod instructions;
-mod state;
-mod errors;
-mod constants;
+pub mod instructions;
+pub mod state;
+pub mod errors;
+pub mod constants;
+mod events;
+mod math;
+mod token;
+mod utils;
 
 use instructions::*;
 
@@ -23,25 +27,92 @@ pub mod solana_uniswap_v2 {
     }
 
     pub fn initialize_pool(
-        ctx: Context<InitializePool>
+        ctx: Context<InitializePool>,
+        fee_bps: Option<u16>
     ) -> Result<()> {
-        ctx.accounts.initialize_pool()
+        ctx.accounts.initialize_pool(fee_bps, &ctx.bumps)
+    }
+
+    pub fn initialize_pool_with_liquidity(
+        ctx: Context<InitializePoolWithLiquidity>,
+        fee_bps: Option<u16>,
+        amount_a: u64,
+        amount_b: u64,
+        min_liquidity_out: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        ctx.accounts.initialize_pool_with_liquidity(
+            fee_bps,
+            amount_a,
+            amount_b,
+            min_liquidity_out,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
     }
 
     pub fn deposit_liquidity(
         ctx: Context<DepositLiquidity>,
         amount_a: u64,
         amount_b: u64,
+        min_liquidity_out: u64,
+        expiry_unix_ts: i64,
     ) -> Result<()> {
-        ctx.accounts
-            .deposit_liquidity(amount_a, amount_b, &ctx.bumps)
+        ctx.accounts.deposit_liquidity(
+            amount_a,
+            amount_b,
+            min_liquidity_out,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn deposit_single_sided(
+        ctx: Context<DepositLiquidity>,
+        deposit_a: bool,
+        amount_in: u64,
+        min_liquidity_out: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        ctx.accounts.deposit_single_sided(
+            deposit_a,
+            amount_in,
+            min_liquidity_out,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
     }
 
     pub fn withdraw_liquidity(
         ctx: Context<WithdrawLiquidity>,
-        amount: u64
+        amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        expiry_unix_ts: i64,
     ) -> Result<()> {
-        ctx.accounts.withdraw_liquidity(amount, &ctx.bumps)
+        ctx.accounts.withdraw_liquidity(
+            amount,
+            min_amount_a,
+            min_amount_b,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn withdraw_single_sided(
+        ctx: Context<WithdrawLiquidity>,
+        receive_a: bool,
+        amount: u64,
+        min_amount_out: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_single_sided(
+            receive_a,
+            amount,
+            min_amount_out,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
     }
     
     pub fn swap(
@@ -49,9 +120,161 @@ pub mod solana_uniswap_v2 {
         swap_a: bool,
         input_amount: u64,
         min_result_amount: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        ctx.accounts.swap(
+            swap_a,
+            input_amount,
+            min_result_amount,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn swap_exact_out(
+        ctx: Context<Swap>,
+        swap_a: bool,
+        output_amount: u64,
+        max_input_amount: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        ctx.accounts.swap_exact_out(
+            swap_a,
+            output_amount,
+            max_input_amount,
+            expiry_unix_ts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        input_amount: u64,
+        min_result_amount: u64,
+        expiry_unix_ts: i64,
+    ) -> Result<()> {
+        ctx.accounts.swap_route(
+            ctx.remaining_accounts,
+            input_amount,
+            min_result_amount,
+            expiry_unix_ts,
+        )
+    }
+
+    pub fn observe(
+        ctx: Context<Observe>,
+        seconds_ago: u32,
+    ) -> Result<Twap> {
+        ctx.accounts.observe(seconds_ago)
+    }
+
+    pub fn set_fee_to(
+        ctx: Context<SetFeeTo>,
+        fee_to: Option<Pubkey>,
+        protocol_fee_share: u8,
+    ) -> Result<()> {
+        ctx.accounts.set_fee_to(fee_to, protocol_fee_share)
+    }
+
+    pub fn set_fee(
+        ctx: Context<SetFee>,
+        fee: u16
+    ) -> Result<()> {
+        ctx.accounts.set_fee(fee)
+    }
+
+    pub fn propose_admin(
+        ctx: Context<ProposeAdmin>,
+        new_admin: Pubkey
+    ) -> Result<()> {
+        ctx.accounts.propose_admin(new_admin)
+    }
+
+    pub fn accept_admin(
+        ctx: Context<AcceptAdmin>
+    ) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn renounce_admin(
+        ctx: Context<RenounceAdmin>
+    ) -> Result<()> {
+        ctx.accounts.renounce_admin()
+    }
+
+    pub fn set_max_pool_fee(
+        ctx: Context<SetMaxPoolFee>,
+        max_pool_fee: u16
+    ) -> Result<()> {
+        ctx.accounts.set_max_pool_fee(max_pool_fee)
+    }
+
+    pub fn set_pool_fee(
+        ctx: Context<SetPoolFee>,
+        fee_bps: Option<u16>
+    ) -> Result<()> {
+        ctx.accounts.set_pool_fee(fee_bps)
+    }
+
+    pub fn pause(
+        ctx: Context<SetPaused>,
+        allow_withdrawals: bool
+    ) -> Result<()> {
+        ctx.accounts.pause(allow_withdrawals)
+    }
+
+    pub fn unpause(
+        ctx: Context<SetPaused>
+    ) -> Result<()> {
+        ctx.accounts.unpause()
+    }
+
+    pub fn flash_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+        amount_a_out: u64,
+        amount_b_out: u64,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.flash_swap(
+            ctx.remaining_accounts,
+            amount_a_out,
+            amount_b_out,
+            data,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn flash_borrow(
+        ctx: Context<FlashBorrow>,
+        amount_a: u64,
+        amount_b: u64,
+    ) -> Result<()> {
+        ctx.accounts.flash_borrow(amount_a, amount_b, &ctx.bumps)
+    }
+
+    pub fn flash_repay(
+        ctx: Context<FlashRepay>
+    ) -> Result<()> {
+        ctx.accounts.flash_repay()
+    }
+
+    pub fn set_flash_fee(
+        ctx: Context<SetFlashFee>,
+        flash_fee_bps: u16
+    ) -> Result<()> {
+        ctx.accounts.set_flash_fee(flash_fee_bps)
+    }
+
+    pub fn sync(
+        ctx: Context<SyncReserves>
+    ) -> Result<()> {
+        ctx.accounts.sync()
+    }
+
+    pub fn skim(
+        ctx: Context<Skim>
     ) -> Result<()> {
-        ctx.accounts
-            .swap(swap_a, input_amount, min_result_amount, &ctx.bumps)
+        ctx.accounts.skim(&ctx.bumps)
     }
 }
 
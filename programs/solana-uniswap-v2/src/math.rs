@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CustomError;
+
+use self::uint_types::U256;
+
+// Declared apart from the anchor prelude whose `Result` clashes with the macro
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod uint_types {
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+/// Computes `a * b / denominator` rounded down with a u128 intermediate
+pub fn mul_div(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    let result = (a as u128 * b as u128)
+        .checked_div(denominator as u128)
+        .ok_or(CustomError::DivisionByZero)?;
+    u64::try_from(result).map_err(|_| error!(CustomError::MathOverflow))
+}
+
+/// Computes `a * b / denominator` rounded up with a u128 intermediate
+pub fn mul_div_ceil(a: u64, b: u64, denominator: u64) -> Result<u64> {
+    if denominator == 0 {
+        return err!(CustomError::DivisionByZero);
+    }
+
+    let result = (a as u128 * b as u128).div_ceil(denominator as u128);
+    u64::try_from(result).map_err(|_| error!(CustomError::MathOverflow))
+}
+
+/// Amount left after deducting a fee in basis points, the fee is rounded up
+/// so that the result never breaks the fee-adjusted invariant
+pub fn amount_after_fee(amount: u64, fee: u16) -> Result<u64> {
+    amount
+        .checked_sub(mul_div_ceil(amount, fee as u64, 10000)?)
+        .ok_or(error!(CustomError::MathOverflow))
+}
+
+/// Output amount for an already taxed input based on constant product formula
+pub fn constant_product_output(taxed_input: u64, reserve_in: u64, reserve_out: u64) -> Result<u64> {
+    let denominator = reserve_in
+        .checked_add(taxed_input)
+        .ok_or(CustomError::MathOverflow)?;
+    mul_div(taxed_input, reserve_out, denominator)
+}
+
+/// Taxed input amount required for `output` based on the inverted constant
+/// product formula, rounded up
+pub fn constant_product_input(output: u64, reserve_in: u64, reserve_out: u64) -> Result<u64> {
+    let remaining_out = reserve_out
+        .checked_sub(output)
+        .ok_or(CustomError::InsufficientLiquidity)?;
+    mul_div_ceil(reserve_in, output, remaining_out)
+}
+
+/// Verifies the fee-adjusted constant product after a swap, charging the fee
+/// on the inputs as in V2:
+/// (x' * d - in_x * fee) * (y' * d - in_y * fee) >= x * y * d^2
+/// with x, y the reserves before the swap, x', y' after it and d = 10000
+pub fn verify_invariant(
+    reserve_a: u64,
+    reserve_b: u64,
+    balance_a: u64,
+    balance_b: u64,
+    amount_a_in: u64,
+    amount_b_in: u64,
+    fee: u16,
+) -> Result<()> {
+    let d = U256::from(10000u64);
+    let fee = U256::from(fee);
+    let adjusted_a = U256::from(balance_a) * d - U256::from(amount_a_in) * fee;
+    let adjusted_b = U256::from(balance_b) * d - U256::from(amount_b_in) * fee;
+    if adjusted_a * adjusted_b < U256::from(reserve_a) * U256::from(reserve_b) * d * d {
+        return err!(CustomError::InvariantError);
+    }
+
+    Ok(())
+}
+
+/// Amount of the other token worth `amount_a` at the current reserves ratio
+pub fn quote(amount_a: u64, reserve_a: u64, reserve_b: u64) -> Result<u64> {
+    mul_div(amount_a, reserve_b, reserve_a)
+}
+
+/// Liquidity tokens minted for a deposit into an existing pool, the smaller
+/// share of the reserves so that an imbalanced deposit donates its excess
+pub fn liquidity_for_deposit(
+    amount_a: u64,
+    amount_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    supply: u64,
+) -> Result<u64> {
+    let liquidity_a = mul_div(amount_a, supply, reserve_a)?;
+    let liquidity_b = mul_div(amount_b, supply, reserve_b)?;
+    Ok(liquidity_a.min(liquidity_b))
+}
+
+/// Liquidity tokens minted for the initial deposit of both tokens
+pub fn liquidity_for_amounts(amount_a: u64, amount_b: u64) -> u64 {
+    // The root of a product of two u64 always fits in a u64
+    sqrt(amount_a as u128 * amount_b as u128) as u64
+}
+
+/// Share of a single sided deposit to swap so that the remainder matches the
+/// post-swap reserves ratio, solving the fee-adjusted constant product for s:
+/// s = (sqrt(r^2 (d + g)^2 + 4 d g a r) - r (d + g)) / 2g
+/// with r the input reserve, a the input amount, d = 10000 and g = d - fee
+pub fn single_sided_swap_amount(amount_in: u64, reserve_in: u64, fee: u16) -> Result<u64> {
+    let (a, r) = (U256::from(amount_in), U256::from(reserve_in));
+    let d = U256::from(10000u64);
+    let g = U256::from(10000 - fee as u64);
+    let b = r * (d + g);
+    let result = ((b * b + U256::from(4u64) * d * g * a * r).integer_sqrt() - b)
+        .checked_div(U256::from(2u64) * g)
+        .ok_or(CustomError::DivisionByZero)?;
+    u64::try_from(result).map_err(|_| error!(CustomError::MathOverflow))
+}
+
+/// Integer square root rounded down
+pub fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = x / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
@@ -5,9 +5,21 @@ use anchor_lang::prelude::*;
 pub struct Amm {
     pub id: Pubkey,
     pub admin: Pubkey,
+    /// Admin proposed by the current admin, awaiting acceptance
+    pub pending_admin: Pubkey,
     pub fee: u16,
+    /// Upper bound for per-pool fee overrides
+    pub max_pool_fee: u16,
+    /// Recipient of the protocol fee, protocol fee is off when unset
+    pub fee_to: Pubkey,
+    /// Protocol takes 1/n of the fees accrued to liquidity providers
+    pub protocol_fee_share: u8,
+    /// Halts swaps and deposits on every pool of the AMM
+    pub paused: bool,
+    /// Halts withdrawals on every pool of the AMM
+    pub withdrawals_paused: bool,
 }
 
 impl Amm {
-    pub const LEN: usize = 8 + 32 + 32 + 2;
-}
\ No newline at end of file
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 2 + 2 + 32 + 1 + 1 + 1;
+}
@@ -1,4 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{self, Mint, MintTo, TokenAccount},
+};
+use fixed::types::U64F64;
+
+use super::Amm;
+use crate::{errors::CustomError, math::sqrt};
+
+/// Number of price observations kept on each pool
+pub const OBSERVATION_CAPACITY: usize = 8;
 
 #[account]
 #[derive(Default)]
@@ -6,8 +17,205 @@ pub struct Pool {
     pub amm: Pubkey,
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
+    /// Time weighted sum of the price of A in B (UQ64.64), wraps on overflow
+    pub price_a_cumulative: u128,
+    /// Time weighted sum of the price of B in A (UQ64.64), wraps on overflow
+    pub price_b_cumulative: u128,
+    pub last_update_timestamp: i64,
+    pub observation_index: u8,
+    pub observations: [Observation; OBSERVATION_CAPACITY],
+    /// Reserves product as of the most recent liquidity event, used for the protocol fee
+    pub k_last: u128,
+    /// Fee override for this pool, falls back to the AMM fee when unset
+    pub fee_bps: Option<u16>,
+    /// Halts swaps and deposits on this pool
+    pub paused: bool,
+    /// Halts withdrawals on this pool
+    pub withdrawals_paused: bool,
+    /// Fee charged on flash loans, in basis points of the borrowed amounts
+    pub flash_fee_bps: u16,
+    /// Set while a flash loan is outstanding, blocks every other pool handler
+    pub flash_locked: bool,
+    /// Amounts of the outstanding flash loan
+    pub flash_amount_a: u64,
+    pub flash_amount_b: u64,
+    /// Tracked token balances backing the pool, tokens sent directly to the
+    /// pool token accounts are excluded until synced
+    pub reserve_a: u64,
+    pub reserve_b: u64,
 }
 
 impl Pool {
-    pub const LEN: usize = 8 + 32 + 32 + 32;
+    pub const LEN: usize = 8
+        + 32
+        + 32
+        + 32
+        + 16
+        + 16
+        + 8
+        + 1
+        + Observation::LEN * OBSERVATION_CAPACITY
+        + 16
+        + 3
+        + 1
+        + 1
+        + 2
+        + 1
+        + 8
+        + 8
+        + 8
+        + 8;
+
+    /// Swap fee charged by this pool
+    pub fn effective_fee(&self, amm: &Amm) -> u16 {
+        self.fee_bps.unwrap_or(amm.fee)
+    }
+
+    /// Accumulates prices over the time elapsed since the last update using
+    /// the reserves held before the current instruction, then records it
+    pub fn update_oracle(&mut self, timestamp: i64) {
+        let elapsed = timestamp - self.last_update_timestamp;
+        if elapsed <= 0 {
+            return;
+        }
+
+        if self.reserve_a != 0 && self.reserve_b != 0 {
+            let price_a = U64F64::from_num(self.reserve_b) / U64F64::from_num(self.reserve_a);
+            let price_b = U64F64::from_num(self.reserve_a) / U64F64::from_num(self.reserve_b);
+            self.price_a_cumulative = self
+                .price_a_cumulative
+                .wrapping_add(price_a.to_bits().wrapping_mul(elapsed as u128));
+            self.price_b_cumulative = self
+                .price_b_cumulative
+                .wrapping_add(price_b.to_bits().wrapping_mul(elapsed as u128));
+        }
+        self.last_update_timestamp = timestamp;
+
+        self.observation_index =
+            ((self.observation_index as usize + 1) % OBSERVATION_CAPACITY) as u8;
+        self.observations[self.observation_index as usize] = Observation {
+            timestamp,
+            price_a_cumulative: self.price_a_cumulative,
+            price_b_cumulative: self.price_b_cumulative,
+        };
+    }
+
+    /// Reloads the pool token accounts after the transfers of the current
+    /// instruction and moves the reserves by their balance changes, so that
+    /// tokens sent directly to the pool are never counted as reserves
+    pub fn move_reserves(
+        &mut self,
+        pool_account_a: &mut InterfaceAccount<TokenAccount>,
+        pool_account_b: &mut InterfaceAccount<TokenAccount>,
+    ) -> Result<()> {
+        let (balance_a, balance_b) = (pool_account_a.amount, pool_account_b.amount);
+        pool_account_a.reload()?;
+        pool_account_b.reload()?;
+        self.reserve_a = move_reserve(self.reserve_a, balance_a, pool_account_a.amount)?;
+        self.reserve_b = move_reserve(self.reserve_b, balance_b, pool_account_b.amount)?;
+
+        Ok(())
+    }
+
+    /// Liquidity to mint to the protocol for its 1/n share of the sqrt(k)
+    /// growth since the last liquidity event
+    pub fn protocol_fee_liquidity(
+        &self,
+        reserve_a: u64,
+        reserve_b: u64,
+        supply: u64,
+        protocol_fee_share: u8,
+    ) -> Result<u64> {
+        if self.k_last == 0 {
+            return Ok(0);
+        }
+
+        let root_k = sqrt(reserve_a as u128 * reserve_b as u128);
+        let root_k_last = sqrt(self.k_last);
+        if root_k <= root_k_last {
+            return Ok(0);
+        }
+
+        let numerator = (supply as u128)
+            .checked_mul(root_k - root_k_last)
+            .ok_or(CustomError::MathOverflow)?;
+        let denominator = (protocol_fee_share as u128)
+            .checked_sub(1)
+            .and_then(|share| root_k.checked_mul(share))
+            .and_then(|denominator| denominator.checked_add(root_k_last))
+            .ok_or(CustomError::MathOverflow)?;
+        let liquidity = numerator
+            .checked_div(denominator)
+            .ok_or(CustomError::DivisionByZero)?;
+        u64::try_from(liquidity).map_err(|_| error!(CustomError::MathOverflow))
+    }
+
+    /// Mints the protocol share of the fees accrued since the last liquidity
+    /// event and returns whether the protocol fee is on
+    pub fn mint_protocol_fee<'info>(
+        &mut self,
+        amm: &Amm,
+        mint_liquidity: &mut InterfaceAccount<'info, Mint>,
+        fee_to_account: &InterfaceAccount<'info, TokenAccount>,
+        pool_authority: &AccountInfo<'info>,
+        token_program: &Program<'info, Token>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<bool> {
+        let fee_on = amm.fee_to != Pubkey::default();
+        if fee_on {
+            let fee_liquidity = self.protocol_fee_liquidity(
+                self.reserve_a,
+                self.reserve_b,
+                mint_liquidity.supply,
+                amm.protocol_fee_share,
+            )?;
+            if fee_liquidity > 0 {
+                token_interface::mint_to(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        MintTo {
+                            mint: mint_liquidity.to_account_info(),
+                            to: fee_to_account.to_account_info(),
+                            authority: pool_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    fee_liquidity,
+                )?;
+                mint_liquidity.reload()?;
+            }
+        } else if self.k_last != 0 {
+            self.k_last = 0;
+        }
+
+        Ok(fee_on)
+    }
+
+    /// Records the reserves product the next protocol fee accrues from
+    pub fn checkpoint_k_last(&mut self, fee_on: bool) {
+        if fee_on {
+            self.k_last = self.reserve_a as u128 * self.reserve_b as u128;
+        }
+    }
+}
+
+/// Moves a reserve by the change between two balances of its token account
+fn move_reserve(reserve: u64, balance_before: u64, balance_after: u64) -> Result<u64> {
+    let reserve = if balance_after >= balance_before {
+        reserve.checked_add(balance_after - balance_before)
+    } else {
+        reserve.checked_sub(balance_before - balance_after)
+    };
+    reserve.ok_or(error!(CustomError::MathOverflow))
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Observation {
+    pub timestamp: i64,
+    pub price_a_cumulative: u128,
+    pub price_b_cumulative: u128,
+}
+
+impl Observation {
+    pub const LEN: usize = 8 + 16 + 16;
 }
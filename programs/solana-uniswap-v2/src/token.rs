@@ -0,0 +1,103 @@
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::{
+    token::spl_token,
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+    token_interface::{self, CloseAccount, SyncNative, TokenAccount, TokenInterface},
+};
+
+use crate::errors::CustomError;
+
+/// Token-2022 transfer fee withheld when sending `amount` of the mint
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(CustomError::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Token-2022 transfer fee to add on top of `amount` so that it is received in full
+pub fn transfer_inverse_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let data = mint.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(CustomError::MathOverflow)?),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Whether the mint is the native SOL mint of either token program
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+/// Whether the account is an empty wrapped SOL account, only holding what it
+/// receives during the instruction
+pub fn is_temporary_wsol(account: &InterfaceAccount<TokenAccount>) -> bool {
+    is_native_mint(&account.mint) && account.amount == 0
+}
+
+/// Wraps the lamports the owner's wrapped SOL account is missing to cover `amount`
+pub fn wrap_sol<'info>(
+    owner: &Signer<'info>,
+    account: &mut InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if !is_native_mint(&account.mint) || account.amount >= amount {
+        return Ok(());
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            system_program::Transfer {
+                from: owner.to_account_info(),
+                to: account.to_account_info(),
+            },
+        ),
+        amount - account.amount,
+    )?;
+    token_interface::sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        SyncNative {
+            account: account.to_account_info(),
+        },
+    ))?;
+    account.reload()
+}
+
+/// Closes the owner's wrapped SOL account, returning its balance as lamports
+pub fn unwrap_sol<'info>(
+    owner: &Signer<'info>,
+    account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: account.to_account_info(),
+            destination: owner.to_account_info(),
+            authority: owner.to_account_info(),
+        },
+    ))
+}
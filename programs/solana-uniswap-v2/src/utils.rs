@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::CustomError;
+
+/// Rejects transactions executed after the user deadline
+pub fn check_expiry(expiry_unix_ts: i64) -> Result<()> {
+    if Clock::get()?.unix_timestamp > expiry_unix_ts {
+        return err!(CustomError::Expired);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::{
+    prelude::Pubkey, solana_program::instruction::Instruction, InstructionData, ToAccountMetas,
+};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::{signature::Keypair, signer::Signer};
+use solana_uniswap_v2::{errors::CustomError, state::Amm};
+
+use super::{account, assert_error, initialize_amm, process, setup, start};
+
+async fn propose_admin(
+    context: &mut ProgramTestContext,
+    amm: Pubkey,
+    new_admin: Pubkey,
+) -> Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: solana_uniswap_v2::ID,
+        accounts: solana_uniswap_v2::accounts::ProposeAdmin {
+            amm,
+            admin: context.payer.pubkey(),
+        }
+        .to_account_metas(None),
+        data: solana_uniswap_v2::instruction::ProposeAdmin { new_admin }.data(),
+    };
+    process(context, &[instruction], &[]).await
+}
+
+async fn accept_admin(
+    context: &mut ProgramTestContext,
+    amm: Pubkey,
+    pending_admin: &Keypair,
+) -> Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: solana_uniswap_v2::ID,
+        accounts: solana_uniswap_v2::accounts::AcceptAdmin {
+            amm,
+            pending_admin: pending_admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: solana_uniswap_v2::instruction::AcceptAdmin {}.data(),
+    };
+    process(context, &[instruction], &[pending_admin]).await
+}
+
+#[tokio::test]
+async fn transfer_admin() {
+    let mut context = start().await;
+    let amm = initialize_amm(&mut context, 500).await.unwrap();
+    let new_admin = Keypair::new();
+
+    propose_admin(&mut context, amm, new_admin.pubkey())
+        .await
+        .unwrap();
+    accept_admin(&mut context, amm, &new_admin).await.unwrap();
+
+    let state = account::<Amm>(&mut context, &amm).await;
+    assert_eq!(state.admin, new_admin.pubkey());
+    assert_eq!(state.pending_admin, Pubkey::default());
+}
+
+#[tokio::test]
+async fn accept_admin_without_proposal() {
+    let mut context = start().await;
+    let amm = initialize_amm(&mut context, 500).await.unwrap();
+
+    assert_error(
+        accept_admin(&mut context, amm, &Keypair::new()).await,
+        CustomError::Unauthorized,
+    );
+}
+
+#[tokio::test]
+async fn set_fee_to_protocol_fee_share_bound() {
+    let (mut context, pool) = setup().await;
+    let fee_to = Pubkey::new_unique();
+
+    assert_error(
+        pool.set_fee_to(&mut context, Some(fee_to), 1).await,
+        CustomError::InvalidFee,
+    );
+    pool.set_fee_to(&mut context, Some(fee_to), 2)
+        .await
+        .unwrap();
+    let amm: Amm = account(&mut context, &pool.amm).await;
+    assert_eq!(amm.fee_to, fee_to);
+    assert_eq!(amm.protocol_fee_share, 2);
+
+    // Clearing the recipient needs no share
+    pool.set_fee_to(&mut context, None, 0).await.unwrap();
+    let amm: Amm = account(&mut context, &pool.amm).await;
+    assert_eq!(amm.fee_to, Pubkey::default());
+}
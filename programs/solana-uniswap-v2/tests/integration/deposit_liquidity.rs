@@ -0,0 +1,276 @@
+use anchor_lang::{prelude::Pubkey, solana_program::system_instruction};
+use anchor_spl::token::spl_token;
+use solana_sdk::signer::Signer;
+use solana_uniswap_v2::{constants::MIN_LIQUIDITY, errors::CustomError};
+
+use super::{
+    assert_error, balance, create_mint, expected_output, initialize_amm, mint_to, process, setup,
+    start, supply, TestPool, FEE, LIQUIDITY, RESERVE_A, RESERVE_B, SUPPLY,
+};
+
+#[tokio::test]
+async fn initial_deposit_locks_minimum_liquidity() {
+    let (mut context, pool) = setup().await;
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_liquidity).await,
+        LIQUIDITY - MIN_LIQUIDITY
+    );
+    pool.assert_reserves(&mut context, RESERVE_A, RESERVE_B)
+        .await;
+}
+
+#[tokio::test]
+async fn deposit_caps_excess_b_to_reserves_ratio() {
+    let (mut context, pool) = setup().await;
+
+    pool.deposit_liquidity(&mut context, 1_000_000, 1_000_000, 500_000)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - 1_000_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B - 250_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_liquidity).await,
+        LIQUIDITY - MIN_LIQUIDITY + 500_000
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + 1_000_000, RESERVE_B + 250_000)
+        .await;
+}
+
+#[tokio::test]
+async fn deposit_caps_excess_a_to_reserves_ratio() {
+    let (mut context, pool) = setup().await;
+
+    pool.deposit_liquidity(&mut context, 4_000_000, 100_000, 200_000)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - 400_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B - 100_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_liquidity).await,
+        LIQUIDITY - MIN_LIQUIDITY + 200_000
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + 400_000, RESERVE_B + 100_000)
+        .await;
+}
+
+#[tokio::test]
+async fn deposit_below_minimum_liquidity() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.deposit_liquidity(&mut context, 1_000_000, 1_000_000, 500_001)
+            .await,
+        CustomError::LiquidityResultUnderflow,
+    );
+}
+
+#[tokio::test]
+async fn initial_deposit_below_minimum_liquidity() {
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, SUPPLY).await;
+
+    assert_error(
+        pool.deposit_liquidity(&mut context, 10, 10, 0).await,
+        CustomError::InsufficientDepositAmount,
+    );
+}
+
+#[tokio::test]
+async fn deposit_single_sided() {
+    let (mut context, pool) = setup().await;
+    let amount = 1_000_000;
+
+    pool.deposit_single_sided(&mut context, true, amount, 0)
+        .await
+        .unwrap();
+
+    // Only A leaves the user, the swapped half never leaves the pool
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - amount
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + amount, RESERVE_B)
+        .await;
+    // Half of the input, less the fee, is swapped to match the pool ratio
+    let fee = 10000 - FEE as u128;
+    let b = RESERVE_A as u128 * (10000 + fee);
+    let swap =
+        ((b * b + 4 * 10000 * fee * amount as u128 * RESERVE_A as u128).isqrt() - b) / (2 * fee);
+    let swap = swap as u64;
+    let output = expected_output(swap, RESERVE_A, RESERVE_B);
+    let liquidity = ((amount - swap) as u128 * LIQUIDITY as u128 / (RESERVE_A + swap) as u128)
+        .min(output as u128 * LIQUIDITY as u128 / (RESERVE_B - output) as u128);
+    assert_eq!(
+        balance(&mut context, &pool.user_account_liquidity).await as u128,
+        (LIQUIDITY - MIN_LIQUIDITY) as u128 + liquidity
+    );
+}
+
+#[tokio::test]
+async fn deposit_single_sided_below_minimum_liquidity() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.deposit_single_sided(&mut context, false, 250_000, LIQUIDITY)
+            .await,
+        CustomError::LiquidityResultUnderflow,
+    );
+}
+
+#[tokio::test]
+async fn deposit_mints_protocol_fee() {
+    let (mut context, mut pool) = setup().await;
+    pool.fee_to = Pubkey::new_unique();
+    pool.set_fee_to(&mut context, Some(pool.fee_to), 6)
+        .await
+        .unwrap();
+    // Records the reserves product the protocol fee accrues from
+    pool.deposit_liquidity(&mut context, 4_000, 1_000, 0)
+        .await
+        .unwrap();
+    pool.swap(&mut context, true, 1_000_000, 0).await.unwrap();
+
+    let state = pool.state(&mut context).await;
+    let supply = supply(&mut context, &pool.mint_liquidity).await as u128;
+    let root_k = (state.reserve_a as u128 * state.reserve_b as u128).isqrt();
+    let root_k_last = state.k_last.isqrt();
+    let fee_liquidity = supply * (root_k - root_k_last) / (5 * root_k + root_k_last);
+    assert!(fee_liquidity > 0);
+
+    pool.deposit_liquidity(&mut context, 4_000, 1_000, 0)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.fee_to_account_liquidity()).await as u128,
+        fee_liquidity
+    );
+}
+
+#[tokio::test]
+async fn deposit_creates_protocol_fee_recipient_account() {
+    let (mut context, mut pool) = setup().await;
+    pool.fee_to = Pubkey::new_unique();
+    pool.set_fee_to(&mut context, Some(pool.fee_to), 6)
+        .await
+        .unwrap();
+    // Records the reserves product the protocol fee accrues from
+    pool.deposit_liquidity(&mut context, 4_000, 1_000, 0)
+        .await
+        .unwrap();
+    pool.swap(&mut context, true, 1_000_000, 0).await.unwrap();
+
+    pool.deposit_liquidity(&mut context, 4_000, 1_000, 0)
+        .await
+        .unwrap();
+
+    assert!(balance(&mut context, &pool.fee_to_account_liquidity()).await > 0);
+}
+
+#[tokio::test]
+async fn deposit_with_wrong_protocol_fee_recipient() {
+    let (mut context, mut pool) = setup().await;
+    pool.set_fee_to(&mut context, Some(Pubkey::new_unique()), 6)
+        .await
+        .unwrap();
+    pool.fee_to = Pubkey::new_unique();
+
+    assert_error(
+        pool.deposit_liquidity(&mut context, 4_000, 1_000, 0).await,
+        CustomError::InvalidFeeRecipient,
+    );
+}
+
+#[tokio::test]
+async fn deposit_overflowing_reserves_ratio() {
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, 10u64.pow(18)).await;
+    pool.deposit_liquidity(&mut context, 1, 10u64.pow(18), 0)
+        .await
+        .unwrap();
+
+    // B worth 10^10 A at the reserves ratio does not fit a u64
+    assert_error(
+        pool.deposit_liquidity(&mut context, 10u64.pow(10), 1, 0)
+            .await,
+        CustomError::MathOverflow,
+    );
+}
+
+#[tokio::test]
+async fn deposit_after_one_sided_donation() {
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, SUPPLY).await;
+    mint_to(&mut context, &pool.mint_b, &pool.pool_account_b, RESERVE_B).await;
+    pool.sync(&mut context).await.unwrap();
+
+    pool.deposit_liquidity(&mut context, RESERVE_A, RESERVE_B, 0)
+        .await
+        .unwrap();
+
+    // The donation sets no price and is shared by the liquidity providers
+    assert_eq!(
+        balance(&mut context, &pool.user_account_liquidity).await,
+        LIQUIDITY - MIN_LIQUIDITY
+    );
+    pool.assert_reserves(&mut context, RESERVE_A, 2 * RESERVE_B)
+        .await;
+}
+
+#[tokio::test]
+async fn deposit_wraps_only_the_capped_sol_amount() {
+    let mut context = start().await;
+    let amm = initialize_amm(&mut context, FEE).await.unwrap();
+    let mint_b = create_mint(&mut context).await;
+    let pool = TestPool::create(&mut context, amm, spl_token::native_mint::ID, mint_b, 0)
+        .await
+        .unwrap();
+    mint_to(&mut context, &mint_b, &pool.user_account_b, SUPPLY).await;
+    let transfer =
+        system_instruction::transfer(&context.payer.pubkey(), &pool.user.pubkey(), SUPPLY);
+    process(&mut context, &[transfer], &[]).await.unwrap();
+    pool.deposit_liquidity(&mut context, RESERVE_A, RESERVE_B, 0)
+        .await
+        .unwrap();
+
+    let lamports = context
+        .banks_client
+        .get_balance(pool.user.pubkey())
+        .await
+        .unwrap();
+    pool.deposit_liquidity(&mut context, 4_000_000, 100_000, 0)
+        .await
+        .unwrap();
+
+    // Only the SOL matching the reserves ratio leaves the depositor
+    assert_eq!(balance(&mut context, &pool.user_account_a).await, 0);
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(pool.user.pubkey())
+            .await
+            .unwrap(),
+        lamports - 400_000
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + 400_000, RESERVE_B + 100_000)
+        .await;
+}
@@ -0,0 +1,140 @@
+use solana_sdk::signer::Signer;
+use solana_uniswap_v2::errors::CustomError;
+
+use super::{
+    assert_error, balance, process, setup, CALLBACK_PROGRAM, FEE, RESERVE_A, RESERVE_B, SUPPLY,
+};
+
+/// Smallest amount of A paying for `amount_b_out` under the fee-adjusted
+/// invariant
+fn flash_swap_input(amount_b_out: u64) -> u64 {
+    let numerator = RESERVE_A as u128 * amount_b_out as u128 * 10000;
+    let denominator = (RESERVE_B - amount_b_out) as u128 * (10000 - FEE as u128);
+    numerator.div_ceil(denominator) as u64
+}
+
+#[tokio::test]
+async fn flash_borrow_and_repay() {
+    let (mut context, pool) = setup().await;
+    let amount = 1_000_000;
+    // Flash fee defaults to the pool fee
+    let fee = amount * FEE as u64 / 10000;
+
+    process(
+        &mut context,
+        &[
+            pool.flash_borrow_instruction(amount, 0),
+            pool.flash_repay_instruction(),
+        ],
+        &[&pool.user],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - fee
+    );
+    assert!(!pool.state(&mut context).await.flash_locked);
+    pool.assert_reserves(&mut context, RESERVE_A + fee, RESERVE_B)
+        .await;
+}
+
+#[tokio::test]
+async fn flash_borrow_without_repay() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        process(
+            &mut context,
+            &[pool.flash_borrow_instruction(1_000_000, 0)],
+            &[&pool.user],
+        )
+        .await,
+        CustomError::FlashRepayMissing,
+    );
+}
+
+#[tokio::test]
+async fn swap_during_flash_loan() {
+    let (mut context, pool) = setup().await;
+    let swap = pool.swap_instruction(context.payer.pubkey(), true, 1_000_000, 0);
+
+    let result = process(
+        &mut context,
+        &[
+            pool.flash_borrow_instruction(1_000_000, 0),
+            swap,
+            pool.flash_repay_instruction(),
+        ],
+        &[&pool.user],
+    )
+    .await;
+
+    assert_error(result, CustomError::FlashLocked);
+    assert!(!pool.state(&mut context).await.flash_locked);
+}
+
+#[tokio::test]
+async fn flash_repay_without_borrow() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        process(
+            &mut context,
+            &[pool.flash_repay_instruction()],
+            &[&pool.user],
+        )
+        .await,
+        CustomError::NoFlashLoan,
+    );
+}
+
+#[tokio::test]
+async fn flash_swap() {
+    let (mut context, pool) = setup().await;
+    let output = 100_000;
+    let input = flash_swap_input(output);
+
+    pool.flash_swap(&mut context, CALLBACK_PROGRAM, 0, output, input)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - input
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B + output
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + input, RESERVE_B - output)
+        .await;
+}
+
+#[tokio::test]
+async fn flash_swap_calling_back_the_amm() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.flash_swap(&mut context, solana_uniswap_v2::ID, 0, 100_000, 0)
+            .await,
+        CustomError::InvalidCallbackProgram,
+    );
+}
+
+#[tokio::test]
+async fn flash_swap_underpaid() {
+    let (mut context, pool) = setup().await;
+    let output = 100_000;
+    let input = flash_swap_input(output);
+
+    assert_error(
+        pool.flash_swap(&mut context, CALLBACK_PROGRAM, 0, output, input - 1)
+            .await,
+        CustomError::InvariantError,
+    );
+
+    pool.assert_reserves(&mut context, RESERVE_A, RESERVE_B)
+        .await;
+}
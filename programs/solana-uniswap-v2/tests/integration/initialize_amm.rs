@@ -0,0 +1,26 @@
+use solana_sdk::signer::Signer;
+use solana_uniswap_v2::{errors::CustomError, state::Amm};
+
+use super::{account, assert_error, start};
+
+#[tokio::test]
+async fn initialize_amm() {
+    let mut context = start().await;
+
+    let amm = super::initialize_amm(&mut context, 500).await.unwrap();
+
+    let state = account::<Amm>(&mut context, &amm).await;
+    assert_eq!(state.admin, context.payer.pubkey());
+    assert_eq!(state.fee, 500);
+    assert!(!state.paused);
+}
+
+#[tokio::test]
+async fn initialize_amm_with_invalid_fee() {
+    let mut context = start().await;
+
+    assert_error(
+        super::initialize_amm(&mut context, 10000).await.map(|_| ()),
+        CustomError::InvalidFee,
+    );
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::Clock;
+use solana_uniswap_v2::{constants::MIN_LIQUIDITY, errors::CustomError};
+
+use super::{
+    assert_error, create_mint, create_mint_with_permanent_delegate, initialize_amm, start, supply,
+    TestPool,
+};
+
+#[tokio::test]
+async fn initialize_pool() {
+    let mut context = start().await;
+
+    let pool = TestPool::new(&mut context, 500, 0).await;
+
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    let state = pool.state(&mut context).await;
+    assert_eq!(state.amm, pool.amm);
+    assert_eq!(state.mint_a, pool.mint_a);
+    assert_eq!(state.mint_b, pool.mint_b);
+    assert_eq!(state.reserve_a, 0);
+    assert_eq!(state.reserve_b, 0);
+    assert_eq!(state.observations[0].timestamp, clock.unix_timestamp);
+    // Minimum liquidity is locked upfront
+    assert_eq!(
+        supply(&mut context, &pool.mint_liquidity).await,
+        MIN_LIQUIDITY
+    );
+}
+
+#[tokio::test]
+async fn initialize_pool_with_same_mints() {
+    let mut context = start().await;
+    let amm = initialize_amm(&mut context, 500).await.unwrap();
+    let mint = create_mint(&mut context).await;
+
+    assert_error(
+        TestPool::create(&mut context, amm, mint, mint, 0)
+            .await
+            .map(|_| ()),
+        CustomError::InvalidTokenMint,
+    );
+}
+
+#[tokio::test]
+async fn initialize_pool_with_permanent_delegate() {
+    let mut context = start().await;
+    let amm = initialize_amm(&mut context, 500).await.unwrap();
+    let mint_a = create_mint(&mut context).await;
+    let mint_b = create_mint_with_permanent_delegate(&mut context).await;
+
+    assert_error(
+        TestPool::create(&mut context, amm, mint_a, mint_b, 0)
+            .await
+            .map(|_| ()),
+        CustomError::UnsupportedMintExtension,
+    );
+}
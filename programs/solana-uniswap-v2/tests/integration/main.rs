@@ -0,0 +1,891 @@
+//! Integration tests running the program on `solana-program-test` along with
+//! the SPL programs it calls. `cargo test-sbf` loads the program built to
+//! `target/deploy`, while `cargo test` runs it natively in the same runtime
+
+mod admin;
+mod deposit_liquidity;
+mod flash;
+mod initialize_amm;
+mod initialize_pool;
+mod observe;
+mod swap;
+mod swap_route;
+mod sync_skim;
+mod withdraw_liquidity;
+
+use anchor_lang::{
+    prelude::{AccountInfo, AccountMeta, Clock, ProgramError, Pubkey},
+    solana_program::{
+        entrypoint::ProgramResult, instruction::Instruction, program::invoke, program_pack::Pack,
+        system_instruction, system_program, sysvar,
+    },
+    AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id},
+    token::spl_token,
+    token_2022::spl_token_2022::{self, extension::ExtensionType},
+    token_interface::{Mint, TokenAccount},
+};
+use solana_program_runtime::invoke_context::BuiltinFunctionWithContext;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use solana_uniswap_v2::{
+    constants::{AUTHORITY_SEED, LIQUIDITY_SEED, LOCKED_LIQUIDITY_SEED},
+    errors::CustomError,
+    instructions::Twap,
+    state::Pool,
+};
+
+pub const FEE: u16 = 500;
+pub const SUPPLY: u64 = 100_000_000;
+pub const RESERVE_A: u64 = 4_000_000;
+pub const RESERVE_B: u64 = 1_000_000;
+/// Initial liquidity of the `setup` pool, sqrt(RESERVE_A * RESERVE_B)
+pub const LIQUIDITY: u64 = 2_000_000;
+
+/// Deadline passed to instructions that are expected to go through
+pub const EXPIRY: i64 = i64::MAX;
+
+/// Flash swap callback paying the pool back on behalf of the initiator, it
+/// transfers the amount in its data with the accounts
+/// `[source, destination, authority, token_program]`
+pub const CALLBACK_PROGRAM: Pubkey = Pubkey::new_from_array([7; 32]);
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    // Anchor ties the accounts slice to the lifetime of their data, which the
+    // runtime keeps alive for the whole instruction
+    let accounts = unsafe { std::mem::transmute::<&[AccountInfo], &[AccountInfo]>(accounts) };
+    solana_uniswap_v2::entry(program_id, accounts, data)
+}
+
+fn process_callback(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let amount = u64::from_le_bytes(
+        data.try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    invoke(
+        &spl_token::instruction::transfer(
+            accounts[3].key,
+            accounts[0].key,
+            accounts[1].key,
+            accounts[2].key,
+            &[],
+            amount,
+        )?,
+        accounts,
+    )
+}
+
+/// Starts a bank with the AMM, the SPL programs and the flash swap callback,
+/// the payer being the admin of every AMM created by the tests
+pub async fn start() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new(
+        "solana_uniswap_v2",
+        solana_uniswap_v2::ID,
+        processor!(process_instruction),
+    );
+    let callback: Option<BuiltinFunctionWithContext> = processor!(process_callback);
+    program_test.add_builtin_program("flash_swap_callback", CALLBACK_PROGRAM, callback.unwrap());
+    program_test.start_with_context().await
+}
+
+/// Starts a bank with a pool holding `RESERVE_A` and `RESERVE_B`
+pub async fn setup() -> (ProgramTestContext, TestPool) {
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, SUPPLY).await;
+    pool.deposit_liquidity(&mut context, RESERVE_A, RESERVE_B, 0)
+        .await
+        .unwrap();
+    (context, pool)
+}
+
+/// Output of a swap based on constant product formula
+pub fn expected_output(input: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    let taxed_input = (input - (input * FEE as u64).div_ceil(10000)) as u128;
+    (taxed_input * reserve_out as u128 / (reserve_in as u128 + taxed_input)) as u64
+}
+
+/// Input of an exact output swap, rounded up at every step
+pub fn expected_input(output: u64, reserve_in: u64, reserve_out: u64) -> u64 {
+    let taxed_input =
+        (reserve_in as u128 * output as u128).div_ceil((reserve_out - output) as u128);
+    (taxed_input * 10000).div_ceil(10000 - FEE as u128) as u64
+}
+
+/// Sends the instructions in a single transaction paid for by the payer
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    // A fresh blockhash keeps repeated transactions from being deduplicated
+    let blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut keypairs = vec![&context.payer];
+    keypairs.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &keypairs,
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Asserts that a transaction failed with the given program error
+pub fn assert_error(result: Result<(), BanksClientError>, error: CustomError) {
+    match result.map_err(|error| error.unwrap()) {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(code))) => {
+            assert_eq!(code, u32::from(error), "expected {error:?}")
+        }
+        other => panic!("expected {error:?}, got {other:?}"),
+    }
+}
+
+/// Deserializes a program or token account
+pub async fn account<T: AccountDeserialize>(context: &mut ProgramTestContext, key: &Pubkey) -> T {
+    let account = context
+        .banks_client
+        .get_account(*key)
+        .await
+        .unwrap()
+        .unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+pub async fn balance(context: &mut ProgramTestContext, key: &Pubkey) -> u64 {
+    account::<TokenAccount>(context, key).await.amount
+}
+
+pub async fn supply(context: &mut ProgramTestContext, mint: &Pubkey) -> u64 {
+    account::<Mint>(context, mint).await.supply
+}
+
+pub async fn owner(context: &mut ProgramTestContext, key: &Pubkey) -> Pubkey {
+    context
+        .banks_client
+        .get_account(*key)
+        .await
+        .unwrap()
+        .unwrap()
+        .owner
+}
+
+/// Moves the clock forward, as if `seconds` elapsed since the last transaction
+pub async fn warp_time(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Creates an AMM charging `fee` whose admin is the payer
+pub async fn initialize_amm(
+    context: &mut ProgramTestContext,
+    fee: u16,
+) -> Result<Pubkey, BanksClientError> {
+    let id = Pubkey::new_unique();
+    let amm = Pubkey::find_program_address(&[id.as_ref()], &solana_uniswap_v2::ID).0;
+    let instruction = Instruction {
+        program_id: solana_uniswap_v2::ID,
+        accounts: solana_uniswap_v2::accounts::InitializeAmm {
+            amm,
+            admin: context.payer.pubkey(),
+            payer: context.payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: solana_uniswap_v2::instruction::InitializeAmm { id, fee }.data(),
+    };
+    process(context, &[instruction], &[]).await?;
+    Ok(amm)
+}
+
+/// Creates a SPL Token mint whose authority is the payer
+pub async fn create_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &spl_token::ID,
+            &mint.pubkey(),
+            &context.payer.pubkey(),
+            None,
+            6,
+        )
+        .unwrap(),
+    ];
+    process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Creates a Token-2022 mint with the payer as its permanent delegate
+pub async fn create_mint_with_permanent_delegate(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::PermanentDelegate,
+    ])
+    .unwrap();
+    let instructions = [
+        system_instruction::create_account(
+            &context.payer.pubkey(),
+            &mint.pubkey(),
+            rent.minimum_balance(space),
+            space as u64,
+            &spl_token_2022::ID,
+        ),
+        spl_token_2022::instruction::initialize_permanent_delegate(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &context.payer.pubkey(),
+        )
+        .unwrap(),
+        spl_token_2022::instruction::initialize_mint2(
+            &spl_token_2022::ID,
+            &mint.pubkey(),
+            &context.payer.pubkey(),
+            None,
+            6,
+        )
+        .unwrap(),
+    ];
+    process(context, &instructions, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// Creates the associated token account of `owner` and mints `amount` to it
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let token_program = self::owner(context, mint).await;
+    let instruction = spl_associated_token_account::instruction::create_associated_token_account(
+        &context.payer.pubkey(),
+        owner,
+        mint,
+        &token_program,
+    );
+    process(context, &[instruction], &[]).await.unwrap();
+    let account = get_associated_token_address_with_program_id(owner, mint, &token_program);
+    if amount > 0 {
+        mint_to(context, mint, &account, amount).await;
+    }
+    account
+}
+
+pub async fn mint_to(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    account: &Pubkey,
+    amount: u64,
+) {
+    let token_program = owner(context, mint).await;
+    let instruction = spl_token_2022::instruction::mint_to(
+        &token_program,
+        mint,
+        account,
+        &context.payer.pubkey(),
+        &[],
+        amount,
+    )
+    .unwrap();
+    process(context, &[instruction], &[]).await.unwrap();
+}
+
+/// Addresses of a pool along with a funded user
+pub struct TestPool {
+    pub amm: Pubkey,
+    pub pool: Pubkey,
+    pub pool_authority: Pubkey,
+    pub mint_liquidity: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub token_program_a: Pubkey,
+    pub token_program_b: Pubkey,
+    pub pool_account_a: Pubkey,
+    pub pool_account_b: Pubkey,
+    pub user: Keypair,
+    pub user_account_a: Pubkey,
+    pub user_account_b: Pubkey,
+    pub user_account_liquidity: Pubkey,
+    /// Protocol fee recipient passed to liquidity instructions
+    pub fee_to: Pubkey,
+}
+
+impl TestPool {
+    /// Creates an AMM charging `fee` and a pool of two new mints, minting
+    /// `supply` of each to a new user
+    pub async fn new(context: &mut ProgramTestContext, fee: u16, supply: u64) -> Self {
+        let amm = initialize_amm(context, fee).await.unwrap();
+        let mint_a = create_mint(context).await;
+        let mint_b = create_mint(context).await;
+        Self::create(context, amm, mint_a, mint_b, supply)
+            .await
+            .unwrap()
+    }
+
+    /// Initializes the pool of two existing mints, minting `supply` of each to
+    /// a new user
+    pub async fn create(
+        context: &mut ProgramTestContext,
+        amm: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        supply: u64,
+    ) -> Result<Self, BanksClientError> {
+        let program_id = solana_uniswap_v2::ID;
+        let seeds = [amm.as_ref(), mint_a.as_ref(), mint_b.as_ref()];
+        let pool = Pubkey::find_program_address(&seeds, &program_id).0;
+        let pool_authority = Pubkey::find_program_address(
+            &[&seeds[..], &[AUTHORITY_SEED.as_ref()]].concat(),
+            &program_id,
+        )
+        .0;
+        let mint_liquidity = Pubkey::find_program_address(
+            &[&seeds[..], &[LIQUIDITY_SEED.as_ref()]].concat(),
+            &program_id,
+        )
+        .0;
+        let locked_liquidity = Pubkey::find_program_address(
+            &[&seeds[..], &[LOCKED_LIQUIDITY_SEED.as_ref()]].concat(),
+            &program_id,
+        )
+        .0;
+        let token_program_a = owner(context, &mint_a).await;
+        let token_program_b = owner(context, &mint_b).await;
+        let pool_account_a = get_associated_token_address_with_program_id(
+            &pool_authority,
+            &mint_a,
+            &token_program_a,
+        );
+        let pool_account_b = get_associated_token_address_with_program_id(
+            &pool_authority,
+            &mint_b,
+            &token_program_b,
+        );
+        let instruction = Instruction {
+            program_id,
+            accounts: solana_uniswap_v2::accounts::InitializePool {
+                amm,
+                pool,
+                pool_authority,
+                mint_liquidity,
+                locked_liquidity,
+                mint_a,
+                mint_b,
+                pool_account_a,
+                pool_account_b,
+                payer: context.payer.pubkey(),
+                token_program: spl_token::ID,
+                token_program_a,
+                token_program_b,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: solana_uniswap_v2::instruction::InitializePool { fee_bps: None }.data(),
+        };
+        process(context, &[instruction], &[]).await?;
+
+        let user = Keypair::new();
+        let user_account_a = create_token_account(context, &mint_a, &user.pubkey(), supply).await;
+        let user_account_b = create_token_account(context, &mint_b, &user.pubkey(), supply).await;
+        let user_account_liquidity = get_associated_token_address_with_program_id(
+            &user.pubkey(),
+            &mint_liquidity,
+            &spl_token::ID,
+        );
+
+        Ok(Self {
+            amm,
+            pool,
+            pool_authority,
+            mint_liquidity,
+            mint_a,
+            mint_b,
+            token_program_a,
+            token_program_b,
+            pool_account_a,
+            pool_account_b,
+            user,
+            user_account_a,
+            user_account_b,
+            user_account_liquidity,
+            fee_to: Pubkey::default(),
+        })
+    }
+
+    pub async fn state(&self, context: &mut ProgramTestContext) -> Pool {
+        account(context, &self.pool).await
+    }
+
+    /// Asserts that the tracked reserves match the pool vault balances
+    pub async fn assert_reserves(
+        &self,
+        context: &mut ProgramTestContext,
+        reserve_a: u64,
+        reserve_b: u64,
+    ) {
+        let state = self.state(context).await;
+        assert_eq!(state.reserve_a, reserve_a);
+        assert_eq!(state.reserve_b, reserve_b);
+        assert_eq!(balance(context, &self.pool_account_a).await, reserve_a);
+        assert_eq!(balance(context, &self.pool_account_b).await, reserve_b);
+    }
+
+    /// Liquidity account of the protocol fee recipient
+    pub fn fee_to_account_liquidity(&self) -> Pubkey {
+        get_associated_token_address_with_program_id(
+            &self.fee_to,
+            &self.mint_liquidity,
+            &spl_token::ID,
+        )
+    }
+
+    /// Accounts of both `deposit_liquidity` and `deposit_single_sided`
+    fn deposit_accounts(&self, payer: Pubkey) -> Vec<AccountMeta> {
+        solana_uniswap_v2::accounts::DepositLiquidity {
+            amm: self.amm,
+            pool: self.pool,
+            pool_authority: self.pool_authority,
+            depositor: self.user.pubkey(),
+            mint_liquidity: self.mint_liquidity,
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            pool_account_a: self.pool_account_a,
+            pool_account_b: self.pool_account_b,
+            depositor_account_liquidity: self.user_account_liquidity,
+            depositor_account_a: self.user_account_a,
+            depositor_account_b: self.user_account_b,
+            fee_to: self.fee_to,
+            fee_to_account_liquidity: self.fee_to_account_liquidity(),
+            payer,
+            token_program: spl_token::ID,
+            token_program_a: self.token_program_a,
+            token_program_b: self.token_program_b,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None)
+    }
+
+    /// Accounts of both `withdraw_liquidity` and `withdraw_single_sided`
+    fn withdraw_accounts(&self, payer: Pubkey) -> Vec<AccountMeta> {
+        solana_uniswap_v2::accounts::WithdrawLiquidity {
+            amm: self.amm,
+            pool: self.pool,
+            pool_authority: self.pool_authority,
+            depositor: self.user.pubkey(),
+            mint_liquidity: self.mint_liquidity,
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            pool_account_a: self.pool_account_a,
+            pool_account_b: self.pool_account_b,
+            depositor_account_liquidity: self.user_account_liquidity,
+            depositor_account_a: self.user_account_a,
+            depositor_account_b: self.user_account_b,
+            fee_to: self.fee_to,
+            fee_to_account_liquidity: self.fee_to_account_liquidity(),
+            payer,
+            token_program: spl_token::ID,
+            token_program_a: self.token_program_a,
+            token_program_b: self.token_program_b,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None)
+    }
+
+    /// Accounts of both `swap` and `swap_exact_out`
+    pub fn swap_accounts(&self, payer: Pubkey) -> Vec<AccountMeta> {
+        solana_uniswap_v2::accounts::Swap {
+            amm: self.amm,
+            pool: self.pool,
+            pool_authority: self.pool_authority,
+            trader: self.user.pubkey(),
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            pool_account_a: self.pool_account_a,
+            pool_account_b: self.pool_account_b,
+            trader_account_a: self.user_account_a,
+            trader_account_b: self.user_account_b,
+            payer,
+            token_program_a: self.token_program_a,
+            token_program_b: self.token_program_b,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None)
+    }
+
+    /// Accounts of both `sync` and `skim`, along with the user accounts
+    /// receiving the skimmed tokens
+    fn reserves_accounts(&self, skim: bool) -> Vec<AccountMeta> {
+        if skim {
+            solana_uniswap_v2::accounts::Skim {
+                amm: self.amm,
+                pool: self.pool,
+                pool_authority: self.pool_authority,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                pool_account_a: self.pool_account_a,
+                pool_account_b: self.pool_account_b,
+                recipient_account_a: self.user_account_a,
+                recipient_account_b: self.user_account_b,
+                token_program_a: self.token_program_a,
+                token_program_b: self.token_program_b,
+            }
+            .to_account_metas(None)
+        } else {
+            solana_uniswap_v2::accounts::SyncReserves {
+                amm: self.amm,
+                pool: self.pool,
+                pool_authority: self.pool_authority,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                pool_account_a: self.pool_account_a,
+                pool_account_b: self.pool_account_b,
+                token_program_a: self.token_program_a,
+                token_program_b: self.token_program_b,
+            }
+            .to_account_metas(None)
+        }
+    }
+
+    /// Sends an instruction of the AMM signed by the user
+    async fn process(
+        &self,
+        context: &mut ProgramTestContext,
+        accounts: Vec<AccountMeta>,
+        data: Vec<u8>,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts,
+            data,
+        };
+        process(context, &[instruction], &[&self.user]).await
+    }
+
+    pub async fn deposit_liquidity(
+        &self,
+        context: &mut ProgramTestContext,
+        amount_a: u64,
+        amount_b: u64,
+        min_liquidity_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let accounts = self.deposit_accounts(context.payer.pubkey());
+        let data = solana_uniswap_v2::instruction::DepositLiquidity {
+            amount_a,
+            amount_b,
+            min_liquidity_out,
+            expiry_unix_ts: EXPIRY,
+        }
+        .data();
+        self.process(context, accounts, data).await
+    }
+
+    pub async fn deposit_single_sided(
+        &self,
+        context: &mut ProgramTestContext,
+        deposit_a: bool,
+        amount_in: u64,
+        min_liquidity_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let accounts = self.deposit_accounts(context.payer.pubkey());
+        let data = solana_uniswap_v2::instruction::DepositSingleSided {
+            deposit_a,
+            amount_in,
+            min_liquidity_out,
+            expiry_unix_ts: EXPIRY,
+        }
+        .data();
+        self.process(context, accounts, data).await
+    }
+
+    pub async fn withdraw_liquidity(
+        &self,
+        context: &mut ProgramTestContext,
+        amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+    ) -> Result<(), BanksClientError> {
+        let accounts = self.withdraw_accounts(context.payer.pubkey());
+        let data = solana_uniswap_v2::instruction::WithdrawLiquidity {
+            amount,
+            min_amount_a,
+            min_amount_b,
+            expiry_unix_ts: EXPIRY,
+        }
+        .data();
+        self.process(context, accounts, data).await
+    }
+
+    pub async fn withdraw_single_sided(
+        &self,
+        context: &mut ProgramTestContext,
+        receive_a: bool,
+        amount: u64,
+        min_amount_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let accounts = self.withdraw_accounts(context.payer.pubkey());
+        let data = solana_uniswap_v2::instruction::WithdrawSingleSided {
+            receive_a,
+            amount,
+            min_amount_out,
+            expiry_unix_ts: EXPIRY,
+        }
+        .data();
+        self.process(context, accounts, data).await
+    }
+
+    pub fn swap_instruction(
+        &self,
+        payer: Pubkey,
+        swap_a: bool,
+        input_amount: u64,
+        min_result_amount: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: self.swap_accounts(payer),
+            data: solana_uniswap_v2::instruction::Swap {
+                swap_a,
+                input_amount,
+                min_result_amount,
+                expiry_unix_ts: EXPIRY,
+            }
+            .data(),
+        }
+    }
+
+    pub async fn swap(
+        &self,
+        context: &mut ProgramTestContext,
+        swap_a: bool,
+        input_amount: u64,
+        min_result_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let instruction = self.swap_instruction(
+            context.payer.pubkey(),
+            swap_a,
+            input_amount,
+            min_result_amount,
+        );
+        process(context, &[instruction], &[&self.user]).await
+    }
+
+    pub async fn swap_exact_out(
+        &self,
+        context: &mut ProgramTestContext,
+        swap_a: bool,
+        output_amount: u64,
+        max_input_amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let accounts = self.swap_accounts(context.payer.pubkey());
+        let data = solana_uniswap_v2::instruction::SwapExactOut {
+            swap_a,
+            output_amount,
+            max_input_amount,
+            expiry_unix_ts: EXPIRY,
+        }
+        .data();
+        self.process(context, accounts, data).await
+    }
+
+    pub fn flash_borrow_instruction(&self, amount_a: u64, amount_b: u64) -> Instruction {
+        Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: solana_uniswap_v2::accounts::FlashBorrow {
+                amm: self.amm,
+                pool: self.pool,
+                pool_authority: self.pool_authority,
+                borrower: self.user.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                pool_account_a: self.pool_account_a,
+                pool_account_b: self.pool_account_b,
+                receiver_account_a: self.user_account_a,
+                receiver_account_b: self.user_account_b,
+                instructions: sysvar::instructions::ID,
+                token_program_a: self.token_program_a,
+                token_program_b: self.token_program_b,
+            }
+            .to_account_metas(None),
+            data: solana_uniswap_v2::instruction::FlashBorrow { amount_a, amount_b }.data(),
+        }
+    }
+
+    pub fn flash_repay_instruction(&self) -> Instruction {
+        Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: solana_uniswap_v2::accounts::FlashRepay {
+                amm: self.amm,
+                pool: self.pool,
+                pool_authority: self.pool_authority,
+                repayer: self.user.pubkey(),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                pool_account_a: self.pool_account_a,
+                pool_account_b: self.pool_account_b,
+                repayer_account_a: self.user_account_a,
+                repayer_account_b: self.user_account_b,
+                token_program_a: self.token_program_a,
+                token_program_b: self.token_program_b,
+            }
+            .to_account_metas(None),
+            data: solana_uniswap_v2::instruction::FlashRepay {}.data(),
+        }
+    }
+
+    /// Flash swaps to the user, `CALLBACK_PROGRAM` then paying `amount_a_in`
+    /// of A back to the pool on their behalf
+    pub async fn flash_swap(
+        &self,
+        context: &mut ProgramTestContext,
+        callback_program: Pubkey,
+        amount_a_out: u64,
+        amount_b_out: u64,
+        amount_a_in: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut accounts = solana_uniswap_v2::accounts::FlashSwap {
+            amm: self.amm,
+            pool: self.pool,
+            pool_authority: self.pool_authority,
+            initiator: self.user.pubkey(),
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            pool_account_a: self.pool_account_a,
+            pool_account_b: self.pool_account_b,
+            receiver_account_a: self.user_account_a,
+            receiver_account_b: self.user_account_b,
+            callback_program,
+            token_program_a: self.token_program_a,
+            token_program_b: self.token_program_b,
+        }
+        .to_account_metas(None);
+        // Accounts handed to the callback
+        accounts.extend([
+            AccountMeta::new(self.user_account_a, false),
+            AccountMeta::new(self.pool_account_a, false),
+            AccountMeta::new_readonly(self.user.pubkey(), true),
+            AccountMeta::new_readonly(self.token_program_a, false),
+        ]);
+        let data = solana_uniswap_v2::instruction::FlashSwap {
+            amount_a_out,
+            amount_b_out,
+            data: amount_a_in.to_le_bytes().to_vec(),
+        }
+        .data();
+        self.process(context, accounts, data).await
+    }
+
+    pub async fn sync(&self, context: &mut ProgramTestContext) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: self.reserves_accounts(false),
+            data: solana_uniswap_v2::instruction::Sync {}.data(),
+        };
+        process(context, &[instruction], &[]).await
+    }
+
+    /// Skims the excess of the pool vaults to the user
+    pub async fn skim(&self, context: &mut ProgramTestContext) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: self.reserves_accounts(true),
+            data: solana_uniswap_v2::instruction::Skim {}.data(),
+        };
+        process(context, &[instruction], &[]).await
+    }
+
+    /// Reads the time weighted average prices over the last `seconds_ago`
+    pub async fn observe(
+        &self,
+        context: &mut ProgramTestContext,
+        seconds_ago: u32,
+    ) -> Result<Twap, BanksClientError> {
+        let instruction = Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: solana_uniswap_v2::accounts::Observe { pool: self.pool }
+                .to_account_metas(None),
+            data: solana_uniswap_v2::instruction::Observe { seconds_ago }.data(),
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+        let simulation = context
+            .banks_client
+            .simulate_transaction(transaction)
+            .await?;
+        simulation
+            .result
+            .unwrap()
+            .map_err(BanksClientError::TransactionError)?;
+        let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+        Ok(Twap::try_from_slice(&return_data.data).unwrap())
+    }
+
+    /// Sets the protocol fee recipient, signed by the AMM admin
+    pub async fn set_fee_to(
+        &self,
+        context: &mut ProgramTestContext,
+        fee_to: Option<Pubkey>,
+        protocol_fee_share: u8,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: solana_uniswap_v2::accounts::SetFeeTo {
+                amm: self.amm,
+                admin: context.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: solana_uniswap_v2::instruction::SetFeeTo {
+                fee_to,
+                protocol_fee_share,
+            }
+            .data(),
+        };
+        process(context, &[instruction], &[]).await
+    }
+
+    /// Pauses every pool of the AMM, signed by the AMM admin
+    pub async fn pause(
+        &self,
+        context: &mut ProgramTestContext,
+        allow_withdrawals: bool,
+    ) -> Result<(), BanksClientError> {
+        let instruction = Instruction {
+            program_id: solana_uniswap_v2::ID,
+            accounts: solana_uniswap_v2::accounts::SetPaused {
+                amm: self.amm,
+                pool: None,
+                admin: context.payer.pubkey(),
+            }
+            .to_account_metas(None),
+            data: solana_uniswap_v2::instruction::Pause { allow_withdrawals }.data(),
+        };
+        process(context, &[instruction], &[]).await
+    }
+}
@@ -0,0 +1,42 @@
+use fixed::types::U64F64;
+use solana_uniswap_v2::errors::CustomError;
+
+use super::{
+    assert_error, expected_output, setup, start, warp_time, TestPool, FEE, RESERVE_A, RESERVE_B,
+};
+
+#[tokio::test]
+async fn observe_before_any_history() {
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, 0).await;
+
+    assert_error(
+        pool.observe(&mut context, 60).await.map(|_| ()),
+        CustomError::InsufficientObservations,
+    );
+}
+
+#[tokio::test]
+async fn observe_prices_over_window() {
+    let (mut context, pool) = setup().await;
+    let input = 1_000_000;
+    let output = expected_output(input, RESERVE_A, RESERVE_B);
+    warp_time(&mut context, 100).await;
+    pool.swap(&mut context, true, input, 0).await.unwrap();
+    warp_time(&mut context, 100).await;
+    // Records the post-swap prices held over the last 100 seconds
+    pool.sync(&mut context).await.unwrap();
+
+    let twap = pool.observe(&mut context, 100).await.unwrap();
+
+    let (reserve_a, reserve_b) = (RESERVE_A + input, RESERVE_B - output);
+    assert_eq!(twap.end_timestamp - twap.start_timestamp, 100);
+    assert_eq!(
+        twap.price_a,
+        (U64F64::from_num(reserve_b) / U64F64::from_num(reserve_a)).to_bits()
+    );
+    assert_eq!(
+        twap.price_b,
+        (U64F64::from_num(reserve_a) / U64F64::from_num(reserve_b)).to_bits()
+    );
+}
@@ -0,0 +1,184 @@
+use anchor_lang::{solana_program::instruction::Instruction, InstructionData};
+use solana_sdk::signer::Signer;
+use solana_uniswap_v2::errors::CustomError;
+
+use super::{
+    assert_error, balance, expected_input, expected_output, process, setup, start, TestPool, FEE,
+    RESERVE_A, RESERVE_B, SUPPLY,
+};
+
+#[tokio::test]
+async fn swap_a_to_b() {
+    let (mut context, pool) = setup().await;
+    let input = 1_000_000;
+    let output = expected_output(input, RESERVE_A, RESERVE_B);
+
+    pool.swap(&mut context, true, input, output).await.unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - input
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B + output
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + input, RESERVE_B - output)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_b_to_a() {
+    let (mut context, pool) = setup().await;
+    let input = 250_000;
+    let output = expected_output(input, RESERVE_B, RESERVE_A);
+
+    pool.swap(&mut context, false, input, output).await.unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A + output
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B - input
+    );
+    pool.assert_reserves(&mut context, RESERVE_A - output, RESERVE_B + input)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_with_fee_rounded_up() {
+    let (mut context, pool) = setup().await;
+    // The fee of this input is not a whole number of tokens
+    let input = 191_919;
+    let output = expected_output(input, RESERVE_B, RESERVE_A);
+
+    pool.swap(&mut context, false, input, output).await.unwrap();
+
+    pool.assert_reserves(&mut context, RESERVE_A - output, RESERVE_B + input)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_with_reserves_above_two_pow_63() {
+    let reserve = 9_300_000_000_000_000_000;
+    let input = 1_000_000;
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, reserve + input).await;
+    pool.deposit_liquidity(&mut context, reserve, reserve, 0)
+        .await
+        .unwrap();
+    let output = expected_output(input, reserve, reserve);
+
+    pool.swap(&mut context, true, input, output).await.unwrap();
+
+    pool.assert_reserves(&mut context, reserve + input, reserve - output)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_below_minimum_output() {
+    let (mut context, pool) = setup().await;
+    let input = 250_000;
+    let output = expected_output(input, RESERVE_B, RESERVE_A);
+
+    assert_error(
+        pool.swap(&mut context, false, input, output + 1).await,
+        CustomError::SwapResultUnderflow,
+    );
+
+    pool.assert_reserves(&mut context, RESERVE_A, RESERVE_B)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_on_empty_pool() {
+    let mut context = start().await;
+    let pool = TestPool::new(&mut context, FEE, SUPPLY).await;
+
+    // Pricing against empty reserves divides by zero
+    assert_error(
+        pool.swap(&mut context, true, 0, 0).await,
+        CustomError::DivisionByZero,
+    );
+}
+
+#[tokio::test]
+async fn swap_after_expiry() {
+    let (mut context, pool) = setup().await;
+
+    let instruction = Instruction {
+        program_id: solana_uniswap_v2::ID,
+        accounts: pool.swap_accounts(context.payer.pubkey()),
+        data: solana_uniswap_v2::instruction::Swap {
+            swap_a: true,
+            input_amount: 1_000_000,
+            min_result_amount: 0,
+            expiry_unix_ts: 0,
+        }
+        .data(),
+    };
+
+    assert_error(
+        process(&mut context, &[instruction], &[&pool.user]).await,
+        CustomError::Expired,
+    );
+}
+
+#[tokio::test]
+async fn swap_while_paused() {
+    let (mut context, pool) = setup().await;
+    pool.pause(&mut context, true).await.unwrap();
+
+    assert_error(
+        pool.swap(&mut context, true, 1_000_000, 0).await,
+        CustomError::Paused,
+    );
+}
+
+#[tokio::test]
+async fn swap_exact_out() {
+    let (mut context, pool) = setup().await;
+    let output = 100_000;
+    let input = expected_input(output, RESERVE_A, RESERVE_B);
+
+    pool.swap_exact_out(&mut context, true, output, input)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A - input
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B + output
+    );
+    pool.assert_reserves(&mut context, RESERVE_A + input, RESERVE_B - output)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_exact_out_above_maximum_input() {
+    let (mut context, pool) = setup().await;
+    let output = 100_000;
+    let input = expected_input(output, RESERVE_A, RESERVE_B);
+
+    assert_error(
+        pool.swap_exact_out(&mut context, true, output, input - 1)
+            .await,
+        CustomError::SwapInputOverflow,
+    );
+}
+
+#[tokio::test]
+async fn swap_exact_out_of_whole_reserve() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.swap_exact_out(&mut context, true, RESERVE_B, u64::MAX)
+            .await,
+        CustomError::InsufficientLiquidity,
+    );
+}
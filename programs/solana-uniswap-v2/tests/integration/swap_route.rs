@@ -0,0 +1,158 @@
+use anchor_lang::{
+    prelude::{AccountMeta, Pubkey},
+    solana_program::{instruction::Instruction, system_program},
+    InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id},
+    token::spl_token,
+};
+use solana_program_test::{BanksClientError, ProgramTestContext};
+use solana_sdk::signer::Signer;
+use solana_uniswap_v2::errors::CustomError;
+
+use super::{
+    assert_error, balance, create_mint, expected_output, owner, process, setup, TestPool, EXPIRY,
+    RESERVE_A, RESERVE_B, SUPPLY,
+};
+
+/// Swaps A of the trader pool into `mint_out` through `hops`, each being a pool along with
+/// whether it is entered through A
+async fn swap_route(
+    context: &mut ProgramTestContext,
+    trader: &TestPool,
+    mint_out: Pubkey,
+    hops: &[(&TestPool, bool)],
+    input_amount: u64,
+    min_result_amount: u64,
+) -> Result<(), BanksClientError> {
+    let token_program_out = owner(context, &mint_out).await;
+    let mut accounts = solana_uniswap_v2::accounts::SwapRoute {
+        amm: trader.amm,
+        trader: trader.user.pubkey(),
+        mint_in: trader.mint_a,
+        mint_out,
+        trader_account_in: trader.user_account_a,
+        trader_account_out: get_associated_token_address_with_program_id(
+            &trader.user.pubkey(),
+            &mint_out,
+            &token_program_out,
+        ),
+        payer: context.payer.pubkey(),
+        token_program_in: trader.token_program_a,
+        token_program_out,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    for (pool, swap_a) in hops {
+        let (pool_account_in, pool_account_out, mint_out, token_program_out) = if *swap_a {
+            (
+                pool.pool_account_a,
+                pool.pool_account_b,
+                pool.mint_b,
+                pool.token_program_b,
+            )
+        } else {
+            (
+                pool.pool_account_b,
+                pool.pool_account_a,
+                pool.mint_a,
+                pool.token_program_a,
+            )
+        };
+        accounts.extend([
+            AccountMeta::new(pool.pool, false),
+            AccountMeta::new_readonly(pool.pool_authority, false),
+            AccountMeta::new(pool_account_in, false),
+            AccountMeta::new(pool_account_out, false),
+            AccountMeta::new_readonly(mint_out, false),
+            AccountMeta::new_readonly(token_program_out, false),
+        ]);
+    }
+    let instruction = Instruction {
+        program_id: solana_uniswap_v2::ID,
+        accounts,
+        data: solana_uniswap_v2::instruction::SwapRoute {
+            input_amount,
+            min_result_amount,
+            expiry_unix_ts: EXPIRY,
+        }
+        .data(),
+    };
+    process(context, &[instruction], &[&trader.user]).await
+}
+
+#[tokio::test]
+async fn swap_route_through_two_pools() {
+    let (mut context, pool_ab) = setup().await;
+    // Second pool of the same AMM trading B against a new mint C
+    let mint_c = create_mint(&mut context).await;
+    let pool_bc = TestPool::create(&mut context, pool_ab.amm, pool_ab.mint_b, mint_c, SUPPLY)
+        .await
+        .unwrap();
+    pool_bc
+        .deposit_liquidity(&mut context, RESERVE_B, RESERVE_A, 0)
+        .await
+        .unwrap();
+    let input = 1_000_000;
+    let output_b = expected_output(input, RESERVE_A, RESERVE_B);
+    let output_c = expected_output(output_b, RESERVE_B, RESERVE_A);
+
+    swap_route(
+        &mut context,
+        &pool_ab,
+        mint_c,
+        &[(&pool_ab, true), (&pool_bc, true)],
+        input,
+        output_c,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool_ab.user_account_a).await,
+        SUPPLY - RESERVE_A - input
+    );
+    let trader_account_c = get_associated_token_address_with_program_id(
+        &pool_ab.user.pubkey(),
+        &mint_c,
+        &spl_token::ID,
+    );
+    assert_eq!(balance(&mut context, &trader_account_c).await, output_c);
+    pool_ab
+        .assert_reserves(&mut context, RESERVE_A + input, RESERVE_B - output_b)
+        .await;
+    pool_bc
+        .assert_reserves(&mut context, RESERVE_B + output_b, RESERVE_A - output_c)
+        .await;
+}
+
+#[tokio::test]
+async fn swap_route_below_minimum_output() {
+    let (mut context, pool) = setup().await;
+    let output = expected_output(1_000_000, RESERVE_A, RESERVE_B);
+
+    assert_error(
+        swap_route(
+            &mut context,
+            &pool,
+            pool.mint_b,
+            &[(&pool, true)],
+            1_000_000,
+            output + 1,
+        )
+        .await,
+        CustomError::SwapResultUnderflow,
+    );
+}
+
+#[tokio::test]
+async fn swap_route_without_hops() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        swap_route(&mut context, &pool, pool.mint_b, &[], 1_000_000, 0).await,
+        CustomError::InvalidRoute,
+    );
+}
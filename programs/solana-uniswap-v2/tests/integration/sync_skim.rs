@@ -0,0 +1,38 @@
+use super::{balance, mint_to, setup, RESERVE_A, RESERVE_B, SUPPLY};
+
+#[tokio::test]
+async fn sync_counts_donations_as_reserves() {
+    let (mut context, pool) = setup().await;
+    mint_to(&mut context, &pool.mint_a, &pool.pool_account_a, 1_000).await;
+    mint_to(&mut context, &pool.mint_b, &pool.pool_account_b, 2_000).await;
+
+    // Donations are excluded from the reserves until synced
+    let state = pool.state(&mut context).await;
+    assert_eq!(state.reserve_a, RESERVE_A);
+    assert_eq!(state.reserve_b, RESERVE_B);
+
+    pool.sync(&mut context).await.unwrap();
+
+    pool.assert_reserves(&mut context, RESERVE_A + 1_000, RESERVE_B + 2_000)
+        .await;
+}
+
+#[tokio::test]
+async fn skim_sends_donations_to_recipient() {
+    let (mut context, pool) = setup().await;
+    mint_to(&mut context, &pool.mint_a, &pool.pool_account_a, 1_000).await;
+    mint_to(&mut context, &pool.mint_b, &pool.pool_account_b, 2_000).await;
+
+    pool.skim(&mut context).await.unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A + 1_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B + 2_000
+    );
+    pool.assert_reserves(&mut context, RESERVE_A, RESERVE_B)
+        .await;
+}
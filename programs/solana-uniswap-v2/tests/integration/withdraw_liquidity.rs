@@ -0,0 +1,140 @@
+use anchor_lang::prelude::Pubkey;
+use solana_uniswap_v2::{constants::MIN_LIQUIDITY, errors::CustomError};
+
+use super::{
+    assert_error, balance, expected_output, setup, LIQUIDITY, RESERVE_A, RESERVE_B, SUPPLY,
+};
+
+#[tokio::test]
+async fn withdraw_liquidity() {
+    let (mut context, pool) = setup().await;
+
+    pool.withdraw_liquidity(&mut context, LIQUIDITY / 2, 2_000_000, 500_000)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A + 2_000_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B + 500_000
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_liquidity).await,
+        LIQUIDITY / 2 - MIN_LIQUIDITY
+    );
+    pool.assert_reserves(&mut context, RESERVE_A - 2_000_000, RESERVE_B - 500_000)
+        .await;
+}
+
+#[tokio::test]
+async fn withdraw_all_but_minimum_liquidity() {
+    let (mut context, pool) = setup().await;
+
+    pool.withdraw_liquidity(&mut context, LIQUIDITY - MIN_LIQUIDITY, 0, 0)
+        .await
+        .unwrap();
+
+    // Locked minimum liquidity keeps its share of the reserves
+    pool.assert_reserves(&mut context, 200, 50).await;
+}
+
+#[tokio::test]
+async fn withdraw_below_minimum_amounts() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.withdraw_liquidity(&mut context, LIQUIDITY / 2, 2_000_001, 0)
+            .await,
+        CustomError::LiquidityResultUnderflow,
+    );
+    assert_error(
+        pool.withdraw_liquidity(&mut context, LIQUIDITY / 2, 0, 500_001)
+            .await,
+        CustomError::LiquidityResultUnderflow,
+    );
+}
+
+#[tokio::test]
+async fn withdraw_single_sided() {
+    let (mut context, pool) = setup().await;
+
+    pool.withdraw_single_sided(&mut context, true, LIQUIDITY / 2, 0)
+        .await
+        .unwrap();
+
+    // The withdrawn B is swapped for A against the remaining reserves
+    let output = 2_000_000 + expected_output(500_000, RESERVE_B - 500_000, RESERVE_A - 2_000_000);
+    assert_eq!(
+        balance(&mut context, &pool.user_account_a).await,
+        SUPPLY - RESERVE_A + output
+    );
+    assert_eq!(
+        balance(&mut context, &pool.user_account_b).await,
+        SUPPLY - RESERVE_B
+    );
+    pool.assert_reserves(&mut context, RESERVE_A - output, RESERVE_B)
+        .await;
+}
+
+#[tokio::test]
+async fn withdraw_single_sided_below_minimum_amount() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.withdraw_single_sided(&mut context, false, LIQUIDITY / 2, 1_000_000)
+            .await,
+        CustomError::LiquidityResultUnderflow,
+    );
+}
+
+#[tokio::test]
+async fn withdraw_single_sided_while_paused() {
+    let (mut context, pool) = setup().await;
+    pool.pause(&mut context, true).await.unwrap();
+
+    assert_error(
+        pool.withdraw_single_sided(&mut context, true, LIQUIDITY / 2, 0)
+            .await,
+        CustomError::Paused,
+    );
+    // Withdrawals in both tokens stay allowed
+    pool.withdraw_liquidity(&mut context, LIQUIDITY / 2, 0, 0)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn withdraw_while_paused_mints_protocol_fee() {
+    let (mut context, mut pool) = setup().await;
+    pool.fee_to = Pubkey::new_unique();
+    pool.set_fee_to(&mut context, Some(pool.fee_to), 6)
+        .await
+        .unwrap();
+    // Records the reserves product the protocol fee accrues from
+    pool.deposit_liquidity(&mut context, 4_000, 1_000, 0)
+        .await
+        .unwrap();
+    pool.swap(&mut context, true, 1_000_000, 0).await.unwrap();
+    pool.pause(&mut context, true).await.unwrap();
+
+    // The recipient account is created on the fly rather than blocking exits
+    pool.withdraw_liquidity(&mut context, LIQUIDITY / 2, 0, 0)
+        .await
+        .unwrap();
+
+    assert!(balance(&mut context, &pool.fee_to_account_liquidity()).await > 0);
+}
+
+#[tokio::test]
+async fn withdraw_single_sided_above_supply() {
+    let (mut context, pool) = setup().await;
+
+    assert_error(
+        pool.withdraw_single_sided(&mut context, true, 2 * LIQUIDITY, 0)
+            .await,
+        CustomError::MathOverflow,
+    );
+}
@@ -0,0 +1,95 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert } from "./utils";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+describe("Admin governance", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+  });
+
+  it("Set fee", async () => {
+    await program.methods
+      .setFee(30)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .signers([values.admin])
+      .rpc();
+
+    const ammAccount = await program.account.amm.fetch(values.ammKey);
+    expect(ammAccount.fee).to.equal(30);
+  });
+
+  it("Should fail to set invalid fee", async () => {
+    await expectRevert(
+      program.methods
+        .setFee(10000)
+        .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+        .signers([values.admin])
+        .rpc()
+    );
+  });
+
+  it("Transfer admin in two steps", async () => {
+    const newAdmin = Keypair.generate();
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .signers([values.admin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ amm: values.ammKey, pendingAdmin: newAdmin.publicKey })
+      .signers([newAdmin])
+      .rpc();
+
+    const ammAccount = await program.account.amm.fetch(values.ammKey);
+    expect(ammAccount.admin.toString()).to.equal(newAdmin.publicKey.toString());
+    expect(ammAccount.pendingAdmin.toString()).to.equal(
+      PublicKey.default.toString()
+    );
+  });
+
+  it("Should fail to accept admin without proposal", async () => {
+    const attacker = Keypair.generate();
+
+    await expectRevert(
+      program.methods
+        .acceptAdmin()
+        .accounts({ amm: values.ammKey, pendingAdmin: attacker.publicKey })
+        .signers([attacker])
+        .rpc()
+    );
+  });
+
+  it("Renounce admin", async () => {
+    await program.methods
+      .renounceAdmin()
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .signers([values.admin])
+      .rpc();
+
+    const ammAccount = await program.account.amm.fetch(values.ammKey);
+    expect(ammAccount.admin.toString()).to.equal(PublicKey.default.toString());
+    await expectRevert(
+      program.methods
+        .setFee(30)
+        .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+        .signers([values.admin])
+        .rpc()
+    );
+  });
+});
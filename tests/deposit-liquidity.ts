@@ -29,14 +29,17 @@ describe("Deposit liquidity", () => {
     });
 
     await program.methods
-      .initializePool()
+      .initializePool(null)
       .accounts({
         amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
       })
@@ -45,14 +48,22 @@ describe("Deposit liquidity", () => {
 
   it("Deposit equal amounts", async () => {
     await program.methods
-      .depositLiquidity(values.depositAmountA, values.depositAmountA)
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountA,
+        new anchor.BN(0),
+        values.expiry
+      )
       .accounts({
+        amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         depositor: values.admin.publicKey,
         mintLiquidity: values.mintLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
         depositorAccountLiquidity: values.liquidityAccount,
@@ -87,14 +98,79 @@ describe("Deposit liquidity", () => {
 
     await expectRevert(
       program.methods
-        .depositLiquidity(amountA, amountB)
+        .depositLiquidity(amountA, amountB, new anchor.BN(0), values.expiry)
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          depositor: values.admin.publicKey,
+          mintLiquidity: values.mintLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          depositorAccountLiquidity: values.liquidityAccount,
+          depositorAccountA: values.holderAccountA,
+          depositorAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+
+  it("Should fail to deposit after expiry", async () => {
+    const expired = new anchor.BN(Math.floor(Date.now() / 1000) - 60);
+
+    await expectRevert(
+      program.methods
+        .depositLiquidity(
+          values.depositAmountA,
+          values.depositAmountA,
+          new anchor.BN(0),
+          expired
+        )
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          depositor: values.admin.publicKey,
+          mintLiquidity: values.mintLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          depositorAccountLiquidity: values.liquidityAccount,
+          depositorAccountA: values.holderAccountA,
+          depositorAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+
+  it("Should fail to deposit below the minimum liquidity", async () => {
+    await expectRevert(
+      program.methods
+        .depositLiquidity(
+          values.depositAmountA,
+          values.depositAmountA,
+          values.depositAmountA,
+          values.expiry
+        )
         .accounts({
+          amm: values.ammKey,
           pool: values.poolKey,
           poolAuthority: values.poolAuthority,
           depositor: values.admin.publicKey,
           mintLiquidity: values.mintLiquidity,
           mintA: values.mintAKeypair.publicKey,
           mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
           poolAccountA: values.poolAccountA,
           poolAccountB: values.poolAccountB,
           depositorAccountLiquidity: values.liquidityAccount,
@@ -0,0 +1,154 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
+
+describe("Deposit single sided", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountA,
+        new anchor.BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  it("Deposit only token A", async () => {
+    await program.methods
+      .depositSingleSided(
+        true,
+        values.depositAmountB,
+        new anchor.BN(1),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const liquidityTokenAccount = await connection.getTokenAccountBalance(
+      values.liquidityAccount
+    );
+    expect(Number(liquidityTokenAccount.value.amount)).to.be.greaterThan(
+      values.depositAmountA.sub(values.minimumLiquidity).toNumber()
+    );
+    const depositTokenAccountA = await connection.getTokenAccountBalance(
+      values.holderAccountA
+    );
+    expect(depositTokenAccountA.value.amount).to.equal(
+      values.defaultSupply
+        .sub(values.depositAmountA)
+        .sub(values.depositAmountB)
+        .toString()
+    );
+    const depositTokenAccountB = await connection.getTokenAccountBalance(
+      values.holderAccountB
+    );
+    expect(depositTokenAccountB.value.amount).to.equal(
+      values.defaultSupply.sub(values.depositAmountA).toString()
+    );
+  });
+
+  it("Should fail to deposit below the minimum liquidity", async () => {
+    await expectRevert(
+      program.methods
+        .depositSingleSided(
+          true,
+          values.depositAmountB,
+          values.depositAmountB,
+          values.expiry
+        )
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          depositor: values.admin.publicKey,
+          mintLiquidity: values.mintLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          depositorAccountLiquidity: values.liquidityAccount,
+          depositorAccountA: values.holderAccountA,
+          depositorAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+});
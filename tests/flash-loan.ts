@@ -0,0 +1,187 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import {
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
+import { BN } from "bn.js";
+
+describe("Flash loan", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountB,
+        new BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  const flashBorrow = (amountB: anchor.BN) =>
+    program.methods
+      .flashBorrow(new BN(0), amountB)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        borrower: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        receiverAccountA: values.holderAccountA,
+        receiverAccountB: values.holderAccountB,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .instruction();
+
+  const flashRepay = () =>
+    program.methods
+      .flashRepay()
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        repayer: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        repayerAccountA: values.holderAccountA,
+        repayerAccountB: values.holderAccountB,
+      })
+      .instruction();
+
+  const send = (...instructions: TransactionInstruction[]) =>
+    sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(...instructions),
+      [values.admin],
+      { skipPreflight: true }
+    );
+
+  it("Flash borrow and repay with fee in one transaction", async () => {
+    const amount = new BN(10 ** 5);
+    await send(await flashBorrow(amount), await flashRepay());
+
+    const fee = amount.muln(values.fee).add(new BN(9999)).divn(10000);
+    const poolAccountB = await connection.getTokenAccountBalance(
+      values.poolAccountB
+    );
+    expect(poolAccountB.value.amount).to.equal(
+      values.depositAmountB.add(fee).toString()
+    );
+    const poolAccount = await program.account.pool.fetch(values.poolKey);
+    expect(poolAccount.flashLocked).to.equal(false);
+  });
+
+  it("Should fail to flash borrow without repayment", async () => {
+    await expectRevert(send(await flashBorrow(new BN(10 ** 5))));
+  });
+
+  it("Should fail to swap while a flash loan is outstanding", async () => {
+    const swap = await program.methods
+      .swap(true, new BN(10 ** 5), new BN(100), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+        payer: values.admin.publicKey,
+      })
+      .instruction();
+
+    await expectRevert(
+      send(await flashBorrow(new BN(10 ** 5)), swap, await flashRepay())
+    );
+  });
+
+  it("Set flash fee", async () => {
+    await program.methods
+      .setFlashFee(10)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        admin: values.admin.publicKey,
+      })
+      .signers([values.admin])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(values.poolKey);
+    expect(poolAccount.flashFeeBps).to.equal(10);
+  });
+});
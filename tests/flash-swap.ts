@@ -0,0 +1,135 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { createTransferInstruction } from "@solana/spl-token";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
+import { BN } from "bn.js";
+
+describe("Flash swap", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountB,
+        new BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  // The token program itself acts as callback, repaying the pool with a
+  // transfer signed by the initiator
+  const flashSwap = (amountBOut: anchor.BN, repayment: anchor.BN) => {
+    const repay = createTransferInstruction(
+      values.holderAccountB,
+      values.poolAccountB,
+      values.admin.publicKey,
+      BigInt(repayment.toString())
+    );
+    return program.methods
+      .flashSwap(new BN(0), amountBOut, repay.data)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        initiator: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        receiverAccountA: values.holderAccountA,
+        receiverAccountB: values.holderAccountB,
+        callbackProgram: values.tokenProgramB,
+      })
+      .remainingAccounts(repay.keys)
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  };
+
+  it("Flash swap B repaid with fee through the callback", async () => {
+    const amountOut = new BN(10 ** 5);
+    // Repayment must cover the output grossed up by the pool fee
+    const repayment = amountOut
+      .muln(10000)
+      .add(new BN(10000 - values.fee - 1))
+      .divn(10000 - values.fee);
+    await flashSwap(amountOut, repayment);
+
+    const poolAccountB = await connection.getTokenAccountBalance(
+      values.poolAccountB
+    );
+    expect(poolAccountB.value.amount).to.equal(
+      values.depositAmountB.sub(amountOut).add(repayment).toString()
+    );
+  });
+
+  it("Should fail to flash swap when repaid without fee", async () => {
+    const amountOut = new BN(10 ** 5);
+    await expectRevert(flashSwap(amountOut, amountOut));
+  });
+
+  it("Should fail to flash swap the whole reserve", async () => {
+    await expectRevert(
+      flashSwap(values.depositAmountB, values.depositAmountB.muln(2))
+    );
+  });
+});
@@ -1,6 +1,20 @@
 import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
-import { PublicKey } from "@solana/web3.js";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createMint,
+  createInitializePermanentDelegateInstruction,
+  getMintLen,
+} from "@solana/spl-token";
+import {
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
 import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
 import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
 
@@ -31,18 +45,28 @@ describe("Initialize pool", () => {
 
   it("Initializing pool", async () => {
     await program.methods
-      .initializePool()
+      .initializePool(null)
       .accounts({
         amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
       })
       .rpc({ skipPreflight: true });
+
+    const lockedTokenAccount = await connection.getTokenAccountBalance(
+      values.lockedLiquidity
+    );
+    expect(lockedTokenAccount.value.amount).to.equal(
+      values.minimumLiquidity.toString()
+    );
   });
 
   it("Invalid mints", async () => {
@@ -69,18 +93,255 @@ describe("Initialize pool", () => {
 
     await expectRevert(
       program.methods
-        .initializePool()
+        .initializePool(null)
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          mintLiquidity: values.mintLiquidity,
+          lockedLiquidity: values.lockedLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+        })
+        .rpc()
+    );
+  });
+
+  it("Initializing pool with fee override", async () => {
+    await program.methods
+      .initializePool(30)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc({ skipPreflight: true });
+
+    await program.methods
+      .setPoolFee(10)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        admin: values.admin.publicKey,
+      })
+      .signers([values.admin])
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(values.poolKey);
+    expect(poolAccount.feeBps).to.equal(10);
+  });
+
+  it("Should fail to initialize pool with fee above maximum", async () => {
+    await expectRevert(
+      program.methods
+        .initializePool(values.fee + 1)
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          mintLiquidity: values.mintLiquidity,
+          lockedLiquidity: values.lockedLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+        })
+        .rpc()
+    );
+  });
+
+  it("Initializing pool with a Token-2022 mint", async () => {
+    values = createTestData({
+      id: values.id,
+      tokenProgramB: TOKEN_2022_PROGRAM_ID,
+    });
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+      tokenProgramB: values.tokenProgramB,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc({ skipPreflight: true });
+
+    const poolAccountB = await connection.getAccountInfo(values.poolAccountB);
+    expect(poolAccountB.owner.toBase58()).to.equal(
+      TOKEN_2022_PROGRAM_ID.toBase58()
+    );
+  });
+
+  it("Should fail to initialize pool with a permanent delegate mint", async () => {
+    values = createTestData({
+      id: values.id,
+      tokenProgramB: TOKEN_2022_PROGRAM_ID,
+    });
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(values.admin.publicKey, 10 ** 10)
+    );
+    await createMint(
+      connection,
+      values.admin,
+      values.admin.publicKey,
+      null,
+      6,
+      values.mintAKeypair
+    );
+
+    // Mint B carries the permanent delegate extension
+    const mintLen = getMintLen([ExtensionType.PermanentDelegate]);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: values.admin.publicKey,
+          newAccountPubkey: values.mintBKeypair.publicKey,
+          space: mintLen,
+          lamports: await connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializePermanentDelegateInstruction(
+          values.mintBKeypair.publicKey,
+          values.admin.publicKey,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          values.mintBKeypair.publicKey,
+          6,
+          values.admin.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [values.admin, values.mintBKeypair]
+    );
+
+    await expectRevert(
+      program.methods
+        .initializePool(null)
         .accounts({
           amm: values.ammKey,
           pool: values.poolKey,
           poolAuthority: values.poolAuthority,
           mintLiquidity: values.mintLiquidity,
+          lockedLiquidity: values.lockedLiquidity,
           mintA: values.mintAKeypair.publicKey,
           mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
           poolAccountA: values.poolAccountA,
           poolAccountB: values.poolAccountB,
         })
         .rpc()
     );
   });
+
+  it("Initializing pool with liquidity", async () => {
+    await program.methods
+      .initializePoolWithLiquidity(
+        null,
+        values.depositAmountA,
+        values.depositAmountB,
+        new anchor.BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const depositTokenAccountLiquditiy =
+      await connection.getTokenAccountBalance(values.liquidityAccount);
+    expect(depositTokenAccountLiquditiy.value.amount).to.equal(
+      new anchor.BN(2 * 10 ** 6).sub(values.minimumLiquidity).toString()
+    );
+    const poolTokenAccountA = await connection.getTokenAccountBalance(
+      values.poolAccountA
+    );
+    expect(poolTokenAccountA.value.amount).to.equal(
+      values.depositAmountA.toString()
+    );
+    const poolTokenAccountB = await connection.getTokenAccountBalance(
+      values.poolAccountB
+    );
+    expect(poolTokenAccountB.value.amount).to.equal(
+      values.depositAmountB.toString()
+    );
+  });
+
+  it("Should fail to initialize pool with liquidity below the minimum", async () => {
+    await expectRevert(
+      program.methods
+        .initializePoolWithLiquidity(
+          null,
+          values.depositAmountA,
+          values.depositAmountB,
+          values.depositAmountA,
+          values.expiry
+        )
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          depositor: values.admin.publicKey,
+          mintLiquidity: values.mintLiquidity,
+          lockedLiquidity: values.lockedLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          depositorAccountLiquidity: values.liquidityAccount,
+          depositorAccountA: values.holderAccountA,
+          depositorAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
 });
@@ -0,0 +1,119 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, mintingTokens } from "./utils";
+import { BN } from "bn.js";
+
+describe("Large reserves", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  // Reserves whose product overflows a u64
+  const supply = new BN(10).pow(new BN(18));
+  const depositAmount = new BN(10).pow(new BN(17));
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+      mintedAmount: 10 ** 9,
+      decimals: 9,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(depositAmount, depositAmount, new BN(0), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  it("Deposit", async () => {
+    const liquidityTokenAccount = await connection.getTokenAccountBalance(
+      values.liquidityAccount
+    );
+    expect(liquidityTokenAccount.value.amount).to.equal(
+      depositAmount.sub(values.minimumLiquidity).toString()
+    );
+  });
+
+  it("Swap from A to B", async () => {
+    const input = new BN(10).pow(new BN(15));
+    await program.methods
+      .swap(true, input, new BN(1), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const traderTokenAccountA = await connection.getTokenAccountBalance(
+      values.holderAccountA
+    );
+    expect(traderTokenAccountA.value.amount).to.equal(
+      supply.sub(depositAmount).sub(input).toString()
+    );
+    const traderTokenAccountB = await connection.getTokenAccountBalance(
+      values.holderAccountB
+    );
+    expect(
+      new BN(traderTokenAccountB.value.amount).gt(supply.sub(depositAmount))
+    ).to.equal(true);
+  });
+});
@@ -0,0 +1,215 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import {
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { BN } from "bn.js";
+
+describe("Native SOL", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  const depositAmount = new BN(4 * 10 ** 6);
+  const expiry = new BN(Math.floor(Date.now() / 1000) + 60 * 60);
+
+  let admin: Keypair;
+  let ammKey: PublicKey;
+  let mintB: PublicKey;
+  let poolKey: PublicKey;
+  let poolAuthority: PublicKey;
+  let mintLiquidity: PublicKey;
+  let lockedLiquidity: PublicKey;
+  let poolAccountA: PublicKey;
+  let poolAccountB: PublicKey;
+  let holderAccountA: PublicKey;
+  let holderAccountB: PublicKey;
+
+  const depositAccounts = () => ({
+    amm: ammKey,
+    pool: poolKey,
+    poolAuthority,
+    depositor: admin.publicKey,
+    mintLiquidity,
+    mintA: NATIVE_MINT,
+    mintB,
+    tokenProgramA: TOKEN_PROGRAM_ID,
+    tokenProgramB: TOKEN_PROGRAM_ID,
+    poolAccountA,
+    poolAccountB,
+    depositorAccountLiquidity: getAssociatedTokenAddressSync(
+      mintLiquidity,
+      admin.publicKey,
+      true
+    ),
+    depositorAccountA: holderAccountA,
+    depositorAccountB: holderAccountB,
+  });
+
+  const swapAccounts = () => ({
+    amm: ammKey,
+    pool: poolKey,
+    poolAuthority,
+    trader: admin.publicKey,
+    mintA: NATIVE_MINT,
+    mintB,
+    tokenProgramA: TOKEN_PROGRAM_ID,
+    tokenProgramB: TOKEN_PROGRAM_ID,
+    poolAccountA,
+    poolAccountB,
+    traderAccountA: holderAccountA,
+    traderAccountB: holderAccountB,
+  });
+
+  beforeEach(async () => {
+    admin = Keypair.generate();
+    const id = Keypair.generate().publicKey;
+    ammKey = PublicKey.findProgramAddressSync(
+      [id.toBuffer()],
+      program.programId
+    )[0];
+
+    await program.methods
+      .initializeAmm(id, 500)
+      .accounts({ amm: ammKey, admin: admin.publicKey })
+      .rpc();
+
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(admin.publicKey, 10 ** 10)
+    );
+    mintB = await createMint(connection, admin, admin.publicKey, null, 6);
+    holderAccountB = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        admin,
+        mintB,
+        admin.publicKey
+      )
+    ).address;
+    await mintTo(connection, admin, mintB, holderAccountB, admin, 10 ** 8);
+
+    poolKey = PublicKey.findProgramAddressSync(
+      [ammKey.toBuffer(), NATIVE_MINT.toBuffer(), mintB.toBuffer()],
+      program.programId
+    )[0];
+    poolAuthority = PublicKey.findProgramAddressSync(
+      [
+        ammKey.toBuffer(),
+        NATIVE_MINT.toBuffer(),
+        mintB.toBuffer(),
+        Buffer.from("authority"),
+      ],
+      program.programId
+    )[0];
+    mintLiquidity = PublicKey.findProgramAddressSync(
+      [
+        ammKey.toBuffer(),
+        NATIVE_MINT.toBuffer(),
+        mintB.toBuffer(),
+        Buffer.from("liquidity"),
+      ],
+      program.programId
+    )[0];
+    lockedLiquidity = PublicKey.findProgramAddressSync(
+      [
+        ammKey.toBuffer(),
+        NATIVE_MINT.toBuffer(),
+        mintB.toBuffer(),
+        Buffer.from("locked_liquidity"),
+      ],
+      program.programId
+    )[0];
+    poolAccountA = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      poolAuthority,
+      true
+    );
+    poolAccountB = getAssociatedTokenAddressSync(mintB, poolAuthority, true);
+    holderAccountA = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      admin.publicKey,
+      true
+    );
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: ammKey,
+        pool: poolKey,
+        poolAuthority,
+        mintLiquidity,
+        lockedLiquidity,
+        mintA: NATIVE_MINT,
+        mintB,
+        tokenProgramA: TOKEN_PROGRAM_ID,
+        tokenProgramB: TOKEN_PROGRAM_ID,
+        poolAccountA,
+        poolAccountB,
+      })
+      .rpc();
+
+    // Depositor holds no wrapped SOL, lamports are wrapped by the program
+    await program.methods
+      .depositLiquidity(depositAmount, depositAmount, new BN(0), expiry)
+      .accounts(depositAccounts())
+      .signers([admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  it("Deposit wraps lamports", async () => {
+    const poolTokenAccountA = await connection.getTokenAccountBalance(
+      poolAccountA
+    );
+    expect(poolTokenAccountA.value.amount).to.equal(depositAmount.toString());
+    const depositTokenAccountA = await connection.getTokenAccountBalance(
+      holderAccountA
+    );
+    expect(depositTokenAccountA.value.amount).to.equal("0");
+  });
+
+  it("Swap from native SOL", async () => {
+    const input = new BN(10 ** 6);
+    await program.methods
+      .swap(true, input, new BN(100), expiry)
+      .accounts(swapAccounts())
+      .signers([admin])
+      .rpc({ skipPreflight: true });
+
+    const poolTokenAccountA = await connection.getTokenAccountBalance(
+      poolAccountA
+    );
+    expect(poolTokenAccountA.value.amount).to.equal(
+      depositAmount.add(input).toString()
+    );
+    const traderTokenAccountB = await connection.getTokenAccountBalance(
+      holderAccountB
+    );
+    expect(Number(traderTokenAccountB.value.amount)).to.be.greaterThan(
+      10 ** 8 - depositAmount.toNumber()
+    );
+  });
+
+  it("Swap into native SOL unwraps lamports", async () => {
+    const lamportsBefore = await connection.getBalance(admin.publicKey);
+
+    await program.methods
+      .swapExactOut(false, new BN(10 ** 6), new BN(2 * 10 ** 6), expiry)
+      .accounts(swapAccounts())
+      .signers([admin])
+      .rpc({ skipPreflight: true });
+
+    expect(await connection.getAccountInfo(holderAccountA)).to.equal(null);
+    const lamportsAfter = await connection.getBalance(admin.publicKey);
+    expect(lamportsAfter).to.be.greaterThan(lamportsBefore);
+  });
+});
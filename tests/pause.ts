@@ -0,0 +1,180 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
+import { BN } from "bn.js";
+
+describe("Pause", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountB,
+        new BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  const swap = () =>
+    program.methods
+      .swap(true, new BN(10 ** 6), new BN(100), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+  it("Should fail to swap while AMM is paused", async () => {
+    await program.methods
+      .pause(false)
+      .accounts({
+        amm: values.ammKey,
+        pool: null,
+        admin: values.admin.publicKey,
+      })
+      .signers([values.admin])
+      .rpc();
+
+    await expectRevert(swap());
+
+    await program.methods
+      .unpause()
+      .accounts({
+        amm: values.ammKey,
+        pool: null,
+        admin: values.admin.publicKey,
+      })
+      .signers([values.admin])
+      .rpc();
+    await swap();
+  });
+
+  it("Withdraw while pool is paused with withdrawals allowed", async () => {
+    await program.methods
+      .pause(true)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        admin: values.admin.publicKey,
+      })
+      .signers([values.admin])
+      .rpc();
+
+    await expectRevert(swap());
+    await program.methods
+      .withdrawLiquidity(new BN(10 ** 5), new BN(0), new BN(0), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const liquidity = await connection.getTokenAccountBalance(
+      values.liquidityAccount
+    );
+    expect(liquidity.value.amount).to.equal(
+      new BN(2 * 10 ** 6)
+        .sub(values.minimumLiquidity)
+        .sub(new BN(10 ** 5))
+        .toString()
+    );
+  });
+
+  it("Should fail to pause without admin", async () => {
+    const attacker = anchor.web3.Keypair.generate();
+
+    await expectRevert(
+      program.methods
+        .pause(false)
+        .accounts({
+          amm: values.ammKey,
+          pool: null,
+          admin: attacker.publicKey,
+        })
+        .signers([attacker])
+        .rpc()
+    );
+  });
+});
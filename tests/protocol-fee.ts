@@ -0,0 +1,165 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
+import { BN } from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+
+describe("Protocol fee", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+  let treasury: Keypair;
+  let treasuryAccountLiquidity: PublicKey;
+
+  beforeEach(async () => {
+    values = createTestData();
+    treasury = Keypair.generate();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    treasuryAccountLiquidity = (
+      await getOrCreateAssociatedTokenAccount(
+        connection,
+        values.admin,
+        values.mintLiquidity,
+        treasury.publicKey,
+        true
+      )
+    ).address;
+  });
+
+  const deposit = (amountA: anchor.BN, amountB: anchor.BN) =>
+    program.methods
+      .depositLiquidity(amountA, amountB, new BN(0), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+        feeToAccountLiquidity: treasuryAccountLiquidity,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+  it("Set protocol fee recipient", async () => {
+    await program.methods
+      .setFeeTo(treasury.publicKey, 6)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .signers([values.admin])
+      .rpc();
+
+    const ammAccount = await program.account.amm.fetch(values.ammKey);
+    expect(ammAccount.feeTo.toString()).to.equal(treasury.publicKey.toString());
+    expect(ammAccount.protocolFeeShare).to.equal(6);
+  });
+
+  it("Mint protocol fee to recipient on withdrawal after swaps", async () => {
+    await program.methods
+      .setFeeTo(treasury.publicKey, 6)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .signers([values.admin])
+      .rpc();
+
+    await deposit(values.depositAmountA, values.depositAmountB);
+    await program.methods
+      .swap(true, new BN(10 ** 6), new BN(100), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+    await program.methods
+      .withdrawLiquidity(new BN(10 ** 5), new BN(0), new BN(0), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+        feeToAccountLiquidity: treasuryAccountLiquidity,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const treasuryLiquidity = await connection.getTokenAccountBalance(
+      treasuryAccountLiquidity
+    );
+    expect(Number(treasuryLiquidity.value.amount)).to.be.greaterThan(0);
+  });
+
+  it("Should fail to set protocol fee recipient without admin", async () => {
+    const attacker = Keypair.generate();
+
+    await expectRevert(
+      program.methods
+        .setFeeTo(attacker.publicKey, 6)
+        .accounts({ amm: values.ammKey, admin: attacker.publicKey })
+        .signers([attacker])
+        .rpc()
+    );
+  });
+});
@@ -0,0 +1,180 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
+import { BN } from "bn.js";
+
+describe("Swap route", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountB,
+        new BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  it("Swap from A to B through a single hop route", async () => {
+    const input = new BN(10 ** 6);
+    await program.methods
+      .swapRoute(input, new BN(100), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        trader: values.admin.publicKey,
+        mintIn: values.mintAKeypair.publicKey,
+        mintOut: values.mintBKeypair.publicKey,
+        tokenProgramIn: values.tokenProgramA,
+        tokenProgramOut: values.tokenProgramB,
+        traderAccountIn: values.holderAccountA,
+        traderAccountOut: values.holderAccountB,
+      })
+      .remainingAccounts([
+        { pubkey: values.poolKey, isSigner: false, isWritable: true },
+        { pubkey: values.poolAuthority, isSigner: false, isWritable: false },
+        { pubkey: values.poolAccountA, isSigner: false, isWritable: true },
+        { pubkey: values.poolAccountB, isSigner: false, isWritable: true },
+        {
+          pubkey: values.mintBKeypair.publicKey,
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: values.tokenProgramB, isSigner: false, isWritable: false },
+      ])
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const traderTokenAccountA = await connection.getTokenAccountBalance(
+      values.holderAccountA
+    );
+    const traderTokenAccountB = await connection.getTokenAccountBalance(
+      values.holderAccountB
+    );
+    expect(traderTokenAccountA.value.amount).to.equal(
+      values.defaultSupply.sub(values.depositAmountA).sub(input).toString()
+    );
+    expect(Number(traderTokenAccountB.value.amount)).to.be.greaterThan(
+      values.defaultSupply.sub(values.depositAmountB).toNumber()
+    );
+  });
+
+  it("Should fail to swap through a route ending in the wrong mint", async () => {
+    await expectRevert(
+      program.methods
+        .swapRoute(new BN(10 ** 6), new BN(100), values.expiry)
+        .accounts({
+          amm: values.ammKey,
+          trader: values.admin.publicKey,
+          mintIn: values.mintAKeypair.publicKey,
+          mintOut: values.mintAKeypair.publicKey,
+          tokenProgramIn: values.tokenProgramA,
+          tokenProgramOut: values.tokenProgramA,
+          traderAccountIn: values.holderAccountA,
+          traderAccountOut: values.holderAccountA,
+        })
+        .remainingAccounts([
+          { pubkey: values.poolKey, isSigner: false, isWritable: true },
+          { pubkey: values.poolAuthority, isSigner: false, isWritable: false },
+          { pubkey: values.poolAccountA, isSigner: false, isWritable: true },
+          { pubkey: values.poolAccountB, isSigner: false, isWritable: true },
+          {
+            pubkey: values.mintBKeypair.publicKey,
+            isSigner: false,
+            isWritable: false,
+          },
+          { pubkey: values.tokenProgramB, isSigner: false, isWritable: false },
+        ])
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+
+  it("Should fail to swap through a route below the minimum result", async () => {
+    await expectRevert(
+      program.methods
+        .swapRoute(new BN(10 ** 6), values.defaultSupply, values.expiry)
+        .accounts({
+          amm: values.ammKey,
+          trader: values.admin.publicKey,
+          mintIn: values.mintAKeypair.publicKey,
+          mintOut: values.mintBKeypair.publicKey,
+          tokenProgramIn: values.tokenProgramA,
+          tokenProgramOut: values.tokenProgramB,
+          traderAccountIn: values.holderAccountA,
+          traderAccountOut: values.holderAccountB,
+        })
+        .remainingAccounts([
+          { pubkey: values.poolKey, isSigner: false, isWritable: true },
+          { pubkey: values.poolAuthority, isSigner: false, isWritable: false },
+          { pubkey: values.poolAccountA, isSigner: false, isWritable: true },
+          { pubkey: values.poolAccountB, isSigner: false, isWritable: true },
+          {
+            pubkey: values.mintBKeypair.publicKey,
+            isSigner: false,
+            isWritable: false,
+          },
+          { pubkey: values.tokenProgramB, isSigner: false, isWritable: false },
+        ])
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+});
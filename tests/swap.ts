@@ -30,28 +30,39 @@ describe("Swap tokens", () => {
     });
 
     await program.methods
-      .initializePool()
+      .initializePool(null)
       .accounts({
         amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
       })
       .rpc();
 
     await program.methods
-      .depositLiquidity(values.depositAmountA, values.depositAmountB)
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountB,
+        new BN(0),
+        values.expiry
+      )
       .accounts({
+        amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         depositor: values.admin.publicKey,
         mintLiquidity: values.mintLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
         depositorAccountLiquidity: values.liquidityAccount,
@@ -65,7 +76,7 @@ describe("Swap tokens", () => {
   it("Swap from A to B", async () => {
     const input = new BN(10 ** 6);
     await program.methods
-      .swap(true, input, new BN(100))
+      .swap(true, input, new BN(100), values.expiry)
       .accounts({
         amm: values.ammKey,
         pool: values.poolKey,
@@ -73,6 +84,8 @@ describe("Swap tokens", () => {
         trader: values.admin.publicKey,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
         traderAccountA: values.holderAccountA,
@@ -98,12 +111,173 @@ describe("Swap tokens", () => {
     );
   });
 
+  it("Swap from B to A", async () => {
+    const input = new BN(10 ** 5);
+    await program.methods
+      .swap(false, input, new BN(100), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const traderTokenAccountA = await connection.getTokenAccountBalance(
+      values.holderAccountA
+    );
+    const traderTokenAccountB = await connection.getTokenAccountBalance(
+      values.holderAccountB
+    );
+    expect(traderTokenAccountB.value.amount).to.equal(
+      values.defaultSupply.sub(values.depositAmountB).sub(input).toString()
+    );
+    expect(Number(traderTokenAccountA.value.amount)).to.be.greaterThan(
+      values.defaultSupply.sub(values.depositAmountA).toNumber()
+    );
+  });
+
+  it("Emit swap event", async () => {
+    const input = new BN(10 ** 6);
+    let event: any;
+    const listener = program.addEventListener("Swapped", (e) => {
+      event = e;
+    });
+    await program.methods
+      .swap(true, input, new BN(100), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc();
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+    await program.removeEventListener(listener);
+
+    expect(event.pool.toString()).to.equal(values.poolKey.toString());
+    expect(event.amountIn.toString()).to.equal(input.toString());
+    expect(event.feePaid.toString()).to.equal(
+      input.muln(values.fee).divn(10000).toString()
+    );
+    expect(event.reserveA.toString()).to.equal(
+      values.depositAmountA.add(input).toString()
+    );
+  });
+
   it("Should fail to swap with insufficient balance", async () => {
     const excessiveInput = values.defaultSupply.add(new BN(1));
 
     await expectRevert(
       program.methods
-        .swap(true, excessiveInput, new BN(100))
+        .swap(true, excessiveInput, new BN(100), values.expiry)
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          trader: values.admin.publicKey,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          traderAccountA: values.holderAccountA,
+          traderAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+
+  it("Should fail to swap after expiry", async () => {
+    const expired = new BN(Math.floor(Date.now() / 1000) - 60);
+
+    await expectRevert(
+      program.methods
+        .swap(true, new BN(10 ** 6), new BN(100), expired)
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          trader: values.admin.publicKey,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          traderAccountA: values.holderAccountA,
+          traderAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+
+  it("Swap exact output from A to B", async () => {
+    const output = new BN(10 ** 5);
+    await program.methods
+      .swapExactOut(true, output, new BN(10 ** 6), values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const traderTokenAccountA = await connection.getTokenAccountBalance(
+      values.holderAccountA
+    );
+    const traderTokenAccountB = await connection.getTokenAccountBalance(
+      values.holderAccountB
+    );
+    expect(traderTokenAccountB.value.amount).to.equal(
+      values.defaultSupply.sub(values.depositAmountB).add(output).toString()
+    );
+    expect(Number(traderTokenAccountA.value.amount)).to.be.lessThan(
+      values.defaultSupply.sub(values.depositAmountA).toNumber()
+    );
+    expect(Number(traderTokenAccountA.value.amount)).to.be.greaterThan(
+      values.defaultSupply
+        .sub(values.depositAmountA)
+        .sub(new BN(10 ** 6))
+        .toNumber()
+    );
+  });
+
+  it("Should fail to swap exact output above maximum input", async () => {
+    await expectRevert(
+      program.methods
+        .swapExactOut(true, new BN(10 ** 5), new BN(100), values.expiry)
         .accounts({
           amm: values.ammKey,
           pool: values.poolKey,
@@ -111,6 +285,8 @@ describe("Swap tokens", () => {
           trader: values.admin.publicKey,
           mintA: values.mintAKeypair.publicKey,
           mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
           poolAccountA: values.poolAccountA,
           poolAccountB: values.poolAccountB,
           traderAccountA: values.holderAccountA,
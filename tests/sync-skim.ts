@@ -0,0 +1,153 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { mintTo } from "@solana/spl-token";
+import { TestData, createTestData, mintingTokens } from "./utils";
+
+describe("Sync and skim", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  const donation = new anchor.BN(10 ** 6);
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData();
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await mintingTokens({
+      connection,
+      creator: values.admin,
+      mintAKeypair: values.mintAKeypair,
+      mintBKeypair: values.mintBKeypair,
+    });
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountA,
+        new anchor.BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    // Tokens sent directly to the pool are not counted as reserves
+    await mintTo(
+      connection,
+      values.admin,
+      values.mintAKeypair.publicKey,
+      values.poolAccountA,
+      values.admin,
+      donation.toNumber()
+    );
+  });
+
+  it("Donations are excluded from the reserves", async () => {
+    const poolAccount = await program.account.pool.fetch(values.poolKey);
+    expect(poolAccount.reserveA.toString()).to.equal(
+      values.depositAmountA.toString()
+    );
+    expect(poolAccount.reserveB.toString()).to.equal(
+      values.depositAmountA.toString()
+    );
+  });
+
+  it("Sync", async () => {
+    await program.methods
+      .sync()
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+      })
+      .rpc();
+
+    const poolAccount = await program.account.pool.fetch(values.poolKey);
+    expect(poolAccount.reserveA.toString()).to.equal(
+      values.depositAmountA.add(donation).toString()
+    );
+    expect(poolAccount.reserveB.toString()).to.equal(
+      values.depositAmountA.toString()
+    );
+  });
+
+  it("Skim", async () => {
+    await program.methods
+      .skim()
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        recipientAccountA: values.holderAccountA,
+        recipientAccountB: values.holderAccountB,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+      })
+      .rpc();
+
+    const poolTokenAccountA = await connection.getTokenAccountBalance(
+      values.poolAccountA
+    );
+    expect(poolTokenAccountA.value.amount).to.equal(
+      values.depositAmountA.toString()
+    );
+    const recipientTokenAccountA = await connection.getTokenAccountBalance(
+      values.holderAccountA
+    );
+    expect(recipientTokenAccountA.value.amount).to.equal(
+      values.defaultSupply.sub(values.depositAmountA).add(donation).toString()
+    );
+  });
+});
@@ -0,0 +1,246 @@
+import * as anchor from "@project-serum/anchor";
+import { Program } from "@project-serum/anchor";
+import {
+  ExtensionType,
+  TOKEN_2022_PROGRAM_ID,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMint,
+  getMintLen,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
+import { expect } from "chai";
+import { TestData, createTestData, expectRevert } from "./utils";
+import { BN } from "bn.js";
+
+describe("Transfer fee tokens", () => {
+  const provider = anchor.AnchorProvider.env();
+  const connection = provider.connection;
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.SolanaUniswapV2 as Program<SolanaUniswapV2>;
+
+  // Token B withholds 1% of every transfer
+  const transferFeeBps = 100;
+
+  let values: TestData;
+
+  beforeEach(async () => {
+    values = createTestData({ tokenProgramB: TOKEN_2022_PROGRAM_ID });
+
+    await program.methods
+      .initializeAmm(values.id, values.fee)
+      .accounts({ amm: values.ammKey, admin: values.admin.publicKey })
+      .rpc();
+
+    await connection.confirmTransaction(
+      await connection.requestAirdrop(values.admin.publicKey, 10 ** 10)
+    );
+    await createMint(
+      connection,
+      values.admin,
+      values.admin.publicKey,
+      null,
+      6,
+      values.mintAKeypair
+    );
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: values.admin.publicKey,
+          newAccountPubkey: values.mintBKeypair.publicKey,
+          space: mintLen,
+          lamports: await connection.getMinimumBalanceForRentExemption(mintLen),
+          programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+          values.mintBKeypair.publicKey,
+          values.admin.publicKey,
+          values.admin.publicKey,
+          transferFeeBps,
+          BigInt(values.defaultSupply.toString()),
+          TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+          values.mintBKeypair.publicKey,
+          6,
+          values.admin.publicKey,
+          null,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ),
+      [values.admin, values.mintBKeypair]
+    );
+    for (const [mint, tokenProgram] of [
+      [values.mintAKeypair.publicKey, values.tokenProgramA],
+      [values.mintBKeypair.publicKey, values.tokenProgramB],
+    ]) {
+      const account = await getOrCreateAssociatedTokenAccount(
+        connection,
+        values.admin,
+        mint,
+        values.admin.publicKey,
+        true,
+        undefined,
+        undefined,
+        tokenProgram
+      );
+      await mintTo(
+        connection,
+        values.admin,
+        mint,
+        account.address,
+        values.admin.publicKey,
+        BigInt(values.defaultSupply.toString()),
+        undefined,
+        undefined,
+        tokenProgram
+      );
+    }
+
+    await program.methods
+      .initializePool(null)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+      })
+      .rpc();
+
+    await program.methods
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountB,
+        new BN(0),
+        values.expiry
+      )
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        depositor: values.admin.publicKey,
+        mintLiquidity: values.mintLiquidity,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        depositorAccountLiquidity: values.liquidityAccount,
+        depositorAccountA: values.holderAccountA,
+        depositorAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+  });
+
+  const expectedSwapResult = async (input: anchor.BN) => {
+    const reserveA = new BN(
+      (await connection.getTokenAccountBalance(values.poolAccountA)).value
+        .amount
+    );
+    const reserveB = new BN(
+      (await connection.getTokenAccountBalance(values.poolAccountB)).value
+        .amount
+    );
+    const taxedInput = input.sub(input.muln(values.fee).divn(10000));
+    const result = taxedInput.mul(reserveB).div(reserveA.add(taxedInput));
+    const transferFee = result
+      .muln(transferFeeBps)
+      .add(new BN(9999))
+      .divn(10000);
+    return { result, received: result.sub(transferFee) };
+  };
+
+  it("Deposit mints liquidity for amounts net of transfer fees", async () => {
+    const receivedB = values.depositAmountB.sub(
+      values.depositAmountB.muln(transferFeeBps).divn(10000)
+    );
+    const liquidity = Math.floor(
+      Math.sqrt(values.depositAmountA.mul(receivedB).toNumber())
+    );
+
+    const depositTokenAccountLiquditiy =
+      await connection.getTokenAccountBalance(values.liquidityAccount);
+    expect(depositTokenAccountLiquditiy.value.amount).to.equal(
+      new BN(liquidity).sub(values.minimumLiquidity).toString()
+    );
+  });
+
+  it("Swap from A to B pays the trader net of transfer fees", async () => {
+    const input = new BN(10 ** 6);
+    const { received } = await expectedSwapResult(input);
+    const before = new BN(
+      (await connection.getTokenAccountBalance(values.holderAccountB)).value
+        .amount
+    );
+
+    await program.methods
+      .swap(true, input, received, values.expiry)
+      .accounts({
+        amm: values.ammKey,
+        pool: values.poolKey,
+        poolAuthority: values.poolAuthority,
+        trader: values.admin.publicKey,
+        mintA: values.mintAKeypair.publicKey,
+        mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
+        poolAccountA: values.poolAccountA,
+        poolAccountB: values.poolAccountB,
+        traderAccountA: values.holderAccountA,
+        traderAccountB: values.holderAccountB,
+      })
+      .signers([values.admin])
+      .rpc({ skipPreflight: true });
+
+    const after = new BN(
+      (await connection.getTokenAccountBalance(values.holderAccountB)).value
+        .amount
+    );
+    expect(after.sub(before).toString()).to.equal(received.toString());
+  });
+
+  it("Should fail to swap when the fee-less result meets the minimum", async () => {
+    const input = new BN(10 ** 6);
+    const { result } = await expectedSwapResult(input);
+
+    await expectRevert(
+      program.methods
+        .swap(true, input, result, values.expiry)
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          trader: values.admin.publicKey,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          traderAccountA: values.holderAccountA,
+          traderAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
+});
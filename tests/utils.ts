@@ -1,5 +1,6 @@
 import * as anchor from "@project-serum/anchor";
 import {
+  TOKEN_PROGRAM_ID,
   createMint,
   getAssociatedTokenAddressSync,
   getOrCreateAssociatedTokenAccount,
@@ -25,6 +26,8 @@ export const mintingTokens = async ({
   mintBKeypair,
   mintedAmount = 100,
   decimals = 6,
+  tokenProgramA = TOKEN_PROGRAM_ID,
+  tokenProgramB = TOKEN_PROGRAM_ID,
 }: {
   connection: Connection;
   creator: Signer;
@@ -33,6 +36,8 @@ export const mintingTokens = async ({
   mintBKeypair: Keypair;
   mintedAmount?: number;
   decimals?: number;
+  tokenProgramA?: PublicKey;
+  tokenProgramB?: PublicKey;
 }) => {
   // Mint tokens
   await connection.confirmTransaction(
@@ -44,7 +49,9 @@ export const mintingTokens = async ({
     creator.publicKey,
     creator.publicKey,
     decimals,
-    mintAKeypair
+    mintAKeypair,
+    undefined,
+    tokenProgramA
   );
   await createMint(
     connection,
@@ -52,21 +59,29 @@ export const mintingTokens = async ({
     creator.publicKey,
     creator.publicKey,
     decimals,
-    mintBKeypair
+    mintBKeypair,
+    undefined,
+    tokenProgramB
   );
   await getOrCreateAssociatedTokenAccount(
     connection,
     holder,
     mintAKeypair.publicKey,
     holder.publicKey,
-    true
+    true,
+    undefined,
+    undefined,
+    tokenProgramA
   );
   await getOrCreateAssociatedTokenAccount(
     connection,
     holder,
     mintBKeypair.publicKey,
     holder.publicKey,
-    true
+    true,
+    undefined,
+    undefined,
+    tokenProgramB
   );
   await mintTo(
     connection,
@@ -75,10 +90,14 @@ export const mintingTokens = async ({
     getAssociatedTokenAddressSync(
       mintAKeypair.publicKey,
       holder.publicKey,
-      true
+      true,
+      tokenProgramA
     ),
     creator.publicKey,
-    mintedAmount * 10 ** decimals
+    mintedAmount * 10 ** decimals,
+    undefined,
+    undefined,
+    tokenProgramA
   );
   await mintTo(
     connection,
@@ -87,10 +106,14 @@ export const mintingTokens = async ({
     getAssociatedTokenAddressSync(
       mintBKeypair.publicKey,
       holder.publicKey,
-      true
+      true,
+      tokenProgramB
     ),
     creator.publicKey,
-    mintedAmount * 10 ** decimals
+    mintedAmount * 10 ** decimals,
+    undefined,
+    undefined,
+    tokenProgramB
   );
 };
 
@@ -100,12 +123,15 @@ export interface TestData {
   admin: Keypair;
   mintAKeypair: Keypair;
   mintBKeypair: Keypair;
+  tokenProgramA: PublicKey;
+  tokenProgramB: PublicKey;
   defaultSupply: anchor.BN;
   ammKey: PublicKey;
   minimumLiquidity: anchor.BN;
   poolKey: PublicKey;
   poolAuthority: PublicKey;
   mintLiquidity: PublicKey;
+  lockedLiquidity: PublicKey;
   depositAmountA: anchor.BN;
   depositAmountB: anchor.BN;
   liquidityAccount: PublicKey;
@@ -113,6 +139,7 @@ export interface TestData {
   poolAccountB: PublicKey;
   holderAccountA: PublicKey;
   holderAccountB: PublicKey;
+  expiry: anchor.BN;
 }
 
 type TestDataDefaults = {
@@ -121,6 +148,8 @@ type TestDataDefaults = {
 export function createTestData(defaults?: TestDataDefaults): TestData {
   const id = defaults?.id || Keypair.generate().publicKey;
   const admin = Keypair.generate();
+  const tokenProgramA = defaults?.tokenProgramA || TOKEN_PROGRAM_ID;
+  const tokenProgramB = defaults?.tokenProgramB || TOKEN_PROGRAM_ID;
   const ammKey = PublicKey.findProgramAddressSync(
     [id.toBuffer()],
     anchor.workspace.SolanaUniswapV2.programId
@@ -155,6 +184,15 @@ export function createTestData(defaults?: TestDataDefaults): TestData {
     ],
     anchor.workspace.SolanaUniswapV2.programId
   )[0];
+  const lockedLiquidity = PublicKey.findProgramAddressSync(
+    [
+      ammKey.toBuffer(),
+      mintAKeypair.publicKey.toBuffer(),
+      mintBKeypair.publicKey.toBuffer(),
+      Buffer.from("locked_liquidity"),
+    ],
+    anchor.workspace.SolanaUniswapV2.programId
+  )[0];
   const poolKey = PublicKey.findProgramAddressSync(
     [
       ammKey.toBuffer(),
@@ -170,18 +208,23 @@ export function createTestData(defaults?: TestDataDefaults): TestData {
     ammKey,
     mintAKeypair,
     mintBKeypair,
+    tokenProgramA,
+    tokenProgramB,
     mintLiquidity,
+    lockedLiquidity,
     poolKey,
     poolAuthority,
     poolAccountA: getAssociatedTokenAddressSync(
       mintAKeypair.publicKey,
       poolAuthority,
-      true
+      true,
+      tokenProgramA
     ),
     poolAccountB: getAssociatedTokenAddressSync(
       mintBKeypair.publicKey,
       poolAuthority,
-      true
+      true,
+      tokenProgramB
     ),
     liquidityAccount: getAssociatedTokenAddressSync(
       mintLiquidity,
@@ -191,16 +234,19 @@ export function createTestData(defaults?: TestDataDefaults): TestData {
     holderAccountA: getAssociatedTokenAddressSync(
       mintAKeypair.publicKey,
       admin.publicKey,
-      true
+      true,
+      tokenProgramA
     ),
     holderAccountB: getAssociatedTokenAddressSync(
       mintBKeypair.publicKey,
       admin.publicKey,
-      true
+      true,
+      tokenProgramB
     ),
     depositAmountA: new BN(4 * 10 ** 6),
     depositAmountB: new BN(1 * 10 ** 6),
     minimumLiquidity: new BN(100),
     defaultSupply: new BN(100 * 10 ** 6),
+    expiry: new BN(Math.floor(Date.now() / 1000) + 60 * 60),
   };
 }
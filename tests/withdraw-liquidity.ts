@@ -2,7 +2,7 @@ import * as anchor from "@project-serum/anchor";
 import { Program } from "@project-serum/anchor";
 import { SolanaUniswapV2 } from "../target/types/solana_uniswap_v2";
 import { expect } from "chai";
-import { TestData, createTestData, mintingTokens } from "./utils";
+import { TestData, createTestData, expectRevert, mintingTokens } from "./utils";
 
 describe("Withdraw liquidity", () => {
   const provider = anchor.AnchorProvider.env();
@@ -29,28 +29,39 @@ describe("Withdraw liquidity", () => {
     });
 
     await program.methods
-      .initializePool()
+      .initializePool(null)
       .accounts({
         amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         mintLiquidity: values.mintLiquidity,
+        lockedLiquidity: values.lockedLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
       })
       .rpc();
 
     await program.methods
-      .depositLiquidity(values.depositAmountA, values.depositAmountA)
+      .depositLiquidity(
+        values.depositAmountA,
+        values.depositAmountA,
+        new anchor.BN(0),
+        values.expiry
+      )
       .accounts({
+        amm: values.ammKey,
         pool: values.poolKey,
         poolAuthority: values.poolAuthority,
         depositor: values.admin.publicKey,
         mintLiquidity: values.mintLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
         depositorAccountLiquidity: values.liquidityAccount,
@@ -63,7 +74,12 @@ describe("Withdraw liquidity", () => {
 
   it("Withdraw everything", async () => {
     await program.methods
-      .withdrawLiquidity(values.depositAmountA.sub(values.minimumLiquidity))
+      .withdrawLiquidity(
+        values.depositAmountA.sub(values.minimumLiquidity),
+        new anchor.BN(0),
+        new anchor.BN(0),
+        values.expiry
+      )
       .accounts({
         amm: values.ammKey,
         pool: values.poolKey,
@@ -72,6 +88,8 @@ describe("Withdraw liquidity", () => {
         mintLiquidity: values.mintLiquidity,
         mintA: values.mintAKeypair.publicKey,
         mintB: values.mintBKeypair.publicKey,
+        tokenProgramA: values.tokenProgramA,
+        tokenProgramB: values.tokenProgramB,
         poolAccountA: values.poolAccountA,
         poolAccountB: values.poolAccountB,
         depositorAccountLiquidity: values.liquidityAccount,
@@ -104,4 +122,34 @@ describe("Withdraw liquidity", () => {
       values.defaultSupply.sub(values.depositAmountA).toNumber()
     );
   });
+
+  it("Should fail to withdraw below the minimum amounts", async () => {
+    await expectRevert(
+      program.methods
+        .withdrawLiquidity(
+          values.depositAmountA.sub(values.minimumLiquidity),
+          values.depositAmountA,
+          new anchor.BN(0),
+          values.expiry
+        )
+        .accounts({
+          amm: values.ammKey,
+          pool: values.poolKey,
+          poolAuthority: values.poolAuthority,
+          depositor: values.admin.publicKey,
+          mintLiquidity: values.mintLiquidity,
+          mintA: values.mintAKeypair.publicKey,
+          mintB: values.mintBKeypair.publicKey,
+          tokenProgramA: values.tokenProgramA,
+          tokenProgramB: values.tokenProgramB,
+          poolAccountA: values.poolAccountA,
+          poolAccountB: values.poolAccountB,
+          depositorAccountLiquidity: values.liquidityAccount,
+          depositorAccountA: values.holderAccountA,
+          depositorAccountB: values.holderAccountB,
+        })
+        .signers([values.admin])
+        .rpc({ skipPreflight: true })
+    );
+  });
 });